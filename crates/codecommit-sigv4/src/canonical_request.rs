@@ -3,14 +3,13 @@ use std::fmt::Write;
 use hmac::digest::Digest;
 use hmac::digest::Output;
 
-use crate::IntoU256Hex;
 use crate::URL_PATH_PREFIX;
-use crate::hostname::Hostname;
+use crate::hex::IntoU256Hex;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CanonicalRequest<'a> {
     pub repo: &'a str,
-    pub hostname: &'a Hostname<'a>,
+    pub hostname: &'a str,
 }
 
 impl CanonicalRequest<'_> {
@@ -49,13 +48,12 @@ impl core::fmt::Display for CanonicalRequest<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::hostname::InferredHostname;
 
     #[test]
     fn test_to_string() {
         let s = CanonicalRequest {
             repo: "my-repo",
-            hostname: &Hostname::Inferred(InferredHostname::new("us-east-1")),
+            hostname: "git-codecommit.us-east-1.amazonaws.com",
         }
         .to_string();
 
@@ -65,11 +63,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_string_dual_stack() {
+        let s = CanonicalRequest {
+            repo: "my-repo",
+            hostname: "git-codecommit.us-east-1.api.aws",
+        }
+        .to_string();
+
+        assert_eq!(
+            s,
+            "GIT\n/v1/repos/my-repo\n\nhost:git-codecommit.us-east-1.api.aws\n\nhost\n"
+        );
+    }
+
     #[test]
     fn test_sha256() {
         let s = CanonicalRequest {
             repo: "my-repo",
-            hostname: &Hostname::Inferred(InferredHostname::new("us-east-1")),
+            hostname: "git-codecommit.us-east-1.amazonaws.com",
         }
         .sha256()
         .to_string();
@@ -17,6 +17,13 @@ impl core::fmt::Display for TimeWithFormat<'_> {
     }
 }
 
+/// Parses a timestamp in the `SigV4` format, e.g. `20260101T000000`, as UTC.
+pub fn parse_sigv4_timestamp(value: &str) -> Option<SystemTime> {
+    chrono::NaiveDateTime::parse_from_str(value, SIGV4_TIMESTAMP)
+        .ok()
+        .map(|time| time.and_utc().into())
+}
+
 pub trait TimestampExt {
     fn sigv4_date(self) -> TimeWithFormat<'static>;
 
@@ -38,3 +45,25 @@ impl TimestampExt for SystemTime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let time: SystemTime = chrono::DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .expect("valid timestamp")
+            .into();
+        let formatted = time.sigv4_timestamp().to_string();
+        assert_eq!("20260101T000000", formatted);
+        assert_eq!(Some(time), parse_sigv4_timestamp(&formatted));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(None, parse_sigv4_timestamp("2026-01-01T00:00:00"));
+        assert_eq!(None, parse_sigv4_timestamp("20261301T000000"));
+        assert_eq!(None, parse_sigv4_timestamp(""));
+    }
+}
@@ -0,0 +1,313 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+mod canonical_request;
+mod credential_scope;
+mod datetime;
+mod hex;
+mod signer;
+mod string_to_sign;
+mod username;
+mod verify;
+
+use std::time::SystemTime;
+
+use tracing::debug;
+
+use self::canonical_request::CanonicalRequest;
+use self::credential_scope::CredentialScope;
+use self::datetime::TimestampExt;
+use self::datetime::parse_sigv4_timestamp;
+use self::hex::IntoU256Hex;
+pub use self::signer::Signer;
+pub use self::signer::SignerInput;
+pub use self::signer::SignerOutput;
+use self::string_to_sign::StringToSign;
+use self::username::Username;
+pub use self::verify::SecretKeys;
+pub use self::verify::Verifier;
+pub use self::verify::VerifyError;
+
+/// The service name requests are signed for.
+pub const SERVICE: &str = "codecommit";
+
+/// The path under which `CodeCommit` serves repositories, e.g.
+/// `https://git-codecommit.us-east-1.amazonaws.com/v1/repos/<repository>`.
+pub const URL_PATH_PREFIX: &str = "v1/repos";
+
+/// The AWS credentials to sign with.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Credentials<'a> {
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    session_token: Option<&'a str>,
+}
+
+impl<'a> Credentials<'a> {
+    /// Long-term credentials, or temporary ones with
+    /// [`Credentials::with_session_token`].
+    #[must_use]
+    pub fn new(access_key_id: &'a str, secret_access_key: &'a str) -> Self {
+        Self {
+            access_key_id,
+            secret_access_key,
+            session_token: None,
+        }
+    }
+
+    /// Adds the session token of temporary credentials.
+    #[must_use]
+    pub fn with_session_token(mut self, session_token: Option<&'a str>) -> Self {
+        self.session_token = session_token;
+        self
+    }
+}
+
+impl core::fmt::Debug for Credentials<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"** redacted **")
+            .field(
+                "session_token",
+                &self.session_token.map(|_| "** redacted **"),
+            )
+            .finish()
+    }
+}
+
+/// A git request to a `CodeCommit` repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SigningRequest<'a> {
+    timestamp: SystemTime,
+    region: &'a str,
+    host: &'a str,
+    repository: &'a str,
+}
+
+impl<'a> SigningRequest<'a> {
+    /// A request for `repository` in `region`, sent to `host`.
+    ///
+    /// `host` is the host git connects to, or the host a VPC endpoint stands
+    /// in for, e.g. `git-codecommit.us-east-1.amazonaws.com`. It includes the
+    /// port if git connects to a port other than the default for the scheme.
+    ///
+    /// The request is signed at the current time.
+    #[must_use]
+    pub fn new(region: &'a str, host: &'a str, repository: &'a str) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            region,
+            host,
+            repository,
+        }
+    }
+
+    /// Signs the request at `timestamp` instead of the current time.
+    #[must_use]
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Generates the git username and password for this request.
+    #[must_use]
+    pub fn sign(&self, credentials: &Credentials<'_>) -> GitCredentials {
+        match self.sign_with(credentials) {
+            Ok(credentials) => credentials,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Generates the git username and password for this request with an
+    /// external signer.
+    pub fn sign_with<S: Signer + ?Sized>(&self, signer: &S) -> Result<GitCredentials, S::Error> {
+        let Self {
+            timestamp,
+            region,
+            host,
+            repository,
+        } = *self;
+
+        let string_to_sign = StringToSign {
+            timestamp,
+            credential_scope: CredentialScope { timestamp, region },
+            canonical_request: CanonicalRequest {
+                repo: repository,
+                hostname: host,
+            },
+        };
+
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            let canonical_request = string_to_sign.canonical_request.to_string();
+            debug!(?canonical_request, "canonical request for signature");
+        }
+
+        let string_to_sign = string_to_sign.to_string();
+        debug!(?string_to_sign, "string to sign");
+
+        let output = signer.sign(&SignerInput {
+            timestamp,
+            region,
+            string_to_sign: &string_to_sign,
+        })?;
+        debug!(?output, "signed string");
+
+        let username = Username {
+            access_key_id: &output.access_key_id,
+            session_token: output.session_token.as_deref(),
+        }
+        .to_string();
+
+        let password = format!(
+            "{}Z{}",
+            timestamp.sigv4_timestamp(),
+            output.into_signature(&string_to_sign).into_u256_hex()
+        );
+
+        Ok(GitCredentials { username, password })
+    }
+
+    /// The password for this request signed with `secret_access_key`.
+    pub(crate) fn signature(&self, secret_access_key: &str) -> String {
+        self.sign(&Credentials::new("", secret_access_key)).password
+    }
+}
+
+/// The username and password git sends to `CodeCommit` with HTTP basic
+/// authentication.
+///
+/// Both must be percent-encoded to be embedded in a URL: the username
+/// contains `%` and `/` when it carries a session token.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GitCredentials {
+    username: String,
+    password: String,
+}
+
+impl GitCredentials {
+    /// Credentials received from git, e.g. by a server that verifies them
+    /// with a [`Verifier`].
+    #[must_use]
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// The access key ID, followed by `%` and the session token if there is
+    /// one.
+    #[must_use]
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// The signature, prefixed with the timestamp it was signed at.
+    #[must_use]
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// The access key ID the credentials were signed with.
+    #[must_use]
+    pub fn access_key_id(&self) -> &str {
+        Username::parse(&self.username).access_key_id
+    }
+
+    /// The session token of temporary credentials.
+    #[must_use]
+    pub fn session_token(&self) -> Option<&str> {
+        Username::parse(&self.username).session_token
+    }
+
+    /// The time the password was signed at, or `None` if the password is not
+    /// `<timestamp>Z<signature>`.
+    #[must_use]
+    pub fn signed_at(&self) -> Option<SystemTime> {
+        self.split_password().map(|(signed_at, _)| signed_at)
+    }
+
+    fn split_password(&self) -> Option<(SystemTime, &str)> {
+        let (timestamp, signature) = self.password.split_once('Z')?;
+        let is_signature = signature.len() == 64
+            && signature
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+        is_signature.then_some((parse_sigv4_timestamp(timestamp)?, signature))
+    }
+}
+
+impl core::fmt::Debug for GitCredentials {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GitCredentials")
+            .field("access_key_id", &self.access_key_id())
+            .field(
+                "session_token",
+                &self.session_token().map(|_| "** redacted **"),
+            )
+            .field("password", &"** redacted **")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCESS_KEY_ID: &str = "ANOTREAL";
+    const SECRET_ACCESS_KEY: &str = "notrealrnrELgWzOk3IfjzDKtFBhDby";
+
+    #[test]
+    fn test_sign() {
+        let credentials = SigningRequest::new(
+            "us-east-1",
+            "git-codecommit.us-east-1.amazonaws.com",
+            "my-repo",
+        )
+        .with_timestamp(SystemTime::UNIX_EPOCH)
+        .sign(&Credentials::new(ACCESS_KEY_ID, SECRET_ACCESS_KEY));
+
+        assert_eq!("ANOTREAL", credentials.username());
+        assert_eq!(
+            "19700101T000000Zf840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d",
+            credentials.password()
+        );
+    }
+
+    #[test]
+    fn test_sign_with_session_token() {
+        let credentials = SigningRequest::new(
+            "us-east-1",
+            "git-codecommit.us-east-1.amazonaws.com",
+            "my-repo",
+        )
+        .with_timestamp(SystemTime::UNIX_EPOCH)
+        .sign(
+            &Credentials::new(ACCESS_KEY_ID, SECRET_ACCESS_KEY)
+                .with_session_token(Some("notarealsessiontoken")),
+        );
+
+        assert_eq!("ANOTREAL%notarealsessiontoken", credentials.username());
+        assert_eq!(
+            "19700101T000000Zf840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d",
+            credentials.password()
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let credentials = Credentials::new(ACCESS_KEY_ID, SECRET_ACCESS_KEY)
+            .with_session_token(Some("notarealsessiontoken"));
+        let debug = format!("{credentials:?}");
+        assert!(!debug.contains(SECRET_ACCESS_KEY));
+        assert!(!debug.contains("notarealsessiontoken"));
+
+        let git_credentials =
+            SigningRequest::new("us-east-1", "localhost", "my-repo").sign(&credentials);
+        let debug = format!("{git_credentials:?}");
+        assert!(debug.contains(ACCESS_KEY_ID));
+        assert!(!debug.contains("notarealsessiontoken"));
+        assert!(!debug.contains(git_credentials.password()));
+    }
+}
@@ -0,0 +1,237 @@
+use std::convert::Infallible;
+use std::time::SystemTime;
+
+use hmac::KeyInit;
+use hmac::Mac;
+use hmac::digest::FixedOutput;
+
+use crate::Credentials;
+use crate::SERVICE;
+use crate::datetime::TimestampExt;
+
+/// Produces the `SigV4` signature of a request.
+///
+/// [`Credentials`] sign in process. Implement this to keep the secret access
+/// key out of the process, e.g. by asking an agent that holds it.
+pub trait Signer {
+    /// Why signing failed.
+    type Error;
+
+    /// Signs `input`, returning the signature or the signing key for its date,
+    /// region and service.
+    fn sign(&self, input: &SignerInput<'_>) -> Result<SignerOutput, Self::Error>;
+}
+
+impl Signer for Credentials<'_> {
+    type Error = Infallible;
+
+    fn sign(&self, input: &SignerInput<'_>) -> Result<SignerOutput, Self::Error> {
+        let signing_key = aws_sigv4::sign::v4::generate_signing_key(
+            self.secret_access_key,
+            input.timestamp,
+            input.region,
+            SERVICE,
+        );
+
+        Ok(SignerOutput::signature(
+            self.access_key_id,
+            hmac_sha256(signing_key.as_ref(), input.string_to_sign),
+        )
+        .with_session_token(self.session_token))
+    }
+}
+
+/// What a [`Signer`] signs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SignerInput<'a> {
+    pub(crate) timestamp: SystemTime,
+    pub(crate) region: &'a str,
+    pub(crate) string_to_sign: &'a str,
+}
+
+impl SignerInput<'_> {
+    /// The time the request is signed at.
+    #[must_use]
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// [`SignerInput::timestamp`] in the `SigV4` format, e.g.
+    /// `20260101T000000Z`.
+    #[must_use]
+    pub fn amz_date(&self) -> String {
+        format!("{}Z", self.timestamp.sigv4_timestamp())
+    }
+
+    /// The region the request is signed for.
+    #[must_use]
+    pub fn region(&self) -> &str {
+        self.region
+    }
+
+    /// The service the request is signed for, always [`SERVICE`].
+    #[must_use]
+    pub fn service(&self) -> &'static str {
+        SERVICE
+    }
+
+    /// The `SigV4` string to sign.
+    #[must_use]
+    pub fn string_to_sign(&self) -> &str {
+        self.string_to_sign
+    }
+}
+
+/// What a [`Signer`] returns: the identity the request was signed as, and
+/// either the signature or the key to compute it with.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SignerOutput {
+    pub(crate) access_key_id: String,
+    pub(crate) session_token: Option<String>,
+    pub(crate) proof: Proof,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Proof {
+    Signature([u8; 32]),
+    SigningKey([u8; 32]),
+}
+
+impl SignerOutput {
+    /// The HMAC-SHA256 of the string to sign, made by `access_key_id`.
+    #[must_use]
+    pub fn signature(access_key_id: impl Into<String>, signature: [u8; 32]) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            session_token: None,
+            proof: Proof::Signature(signature),
+        }
+    }
+
+    /// The `SigV4` signing key of `access_key_id` for the date, region and
+    /// service of the input. The string to sign is signed with it in process.
+    #[must_use]
+    pub fn signing_key(access_key_id: impl Into<String>, signing_key: [u8; 32]) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            session_token: None,
+            proof: Proof::SigningKey(signing_key),
+        }
+    }
+
+    /// Adds the session token of temporary credentials.
+    #[must_use]
+    pub fn with_session_token(mut self, session_token: Option<impl Into<String>>) -> Self {
+        self.session_token = session_token.map(Into::into);
+        self
+    }
+
+    /// The signature of `string_to_sign`.
+    pub(crate) fn into_signature(self, string_to_sign: &str) -> [u8; 32] {
+        match self.proof {
+            Proof::Signature(signature) => signature,
+            Proof::SigningKey(signing_key) => hmac_sha256(&signing_key, string_to_sign),
+        }
+    }
+}
+
+impl core::fmt::Debug for SignerOutput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let proof = match self.proof {
+            Proof::Signature(_) => "signature",
+            Proof::SigningKey(_) => "signing key",
+        };
+        f.debug_struct("SignerOutput")
+            .field("access_key_id", &self.access_key_id)
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "** redacted **"),
+            )
+            .field("proof", &proof)
+            .finish()
+    }
+}
+
+fn hmac_sha256(key: &[u8], message: &str) -> [u8; 32] {
+    hmac::Hmac::<sha2::Sha256>::new_from_slice(key)
+        .expect("HMAC can take key of any size")
+        .chain_update(message.as_bytes())
+        .finalize_fixed()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SigningRequest;
+
+    const ACCESS_KEY_ID: &str = "ANOTREAL";
+    const SECRET_ACCESS_KEY: &str = "notrealrnrELgWzOk3IfjzDKtFBhDby";
+    const PASSWORD: &str =
+        "19700101T000000Zf840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d";
+
+    /// Hands out signing keys, like an agent that holds the secret key.
+    struct SigningKeyAgent;
+
+    impl Signer for SigningKeyAgent {
+        type Error = Infallible;
+
+        fn sign(&self, input: &SignerInput<'_>) -> Result<SignerOutput, Self::Error> {
+            assert_eq!("19700101T000000Z", input.amz_date());
+            let signing_key = aws_sigv4::sign::v4::generate_signing_key(
+                SECRET_ACCESS_KEY,
+                input.timestamp(),
+                input.region(),
+                input.service(),
+            );
+            let signing_key = signing_key
+                .as_ref()
+                .try_into()
+                .expect("signing key is 32 bytes");
+            Ok(SignerOutput::signing_key(ACCESS_KEY_ID, signing_key)
+                .with_session_token(Some("notarealsessiontoken")))
+        }
+    }
+
+    struct Unavailable;
+
+    impl Signer for Unavailable {
+        type Error = &'static str;
+
+        fn sign(&self, _: &SignerInput<'_>) -> Result<SignerOutput, Self::Error> {
+            Err("agent is not running")
+        }
+    }
+
+    fn request() -> SigningRequest<'static> {
+        SigningRequest::new(
+            "us-east-1",
+            "git-codecommit.us-east-1.amazonaws.com",
+            "my-repo",
+        )
+        .with_timestamp(SystemTime::UNIX_EPOCH)
+    }
+
+    #[test]
+    fn test_signing_key() {
+        let credentials = request().sign_with(&SigningKeyAgent).expect("infallible");
+        assert_eq!("ANOTREAL%notarealsessiontoken", credentials.username());
+        assert_eq!(PASSWORD, credentials.password());
+    }
+
+    #[test]
+    fn test_dyn_signer() {
+        let signer: &dyn Signer<Error = Infallible> =
+            &Credentials::new(ACCESS_KEY_ID, SECRET_ACCESS_KEY);
+        let credentials = request().sign_with(signer).expect("infallible");
+        assert_eq!(PASSWORD, credentials.password());
+    }
+
+    #[test]
+    fn test_signer_error() {
+        assert_eq!(
+            Err("agent is not running"),
+            request().sign_with(&Unavailable)
+        );
+    }
+}
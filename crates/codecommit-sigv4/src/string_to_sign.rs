@@ -1,8 +1,8 @@
 use std::time::SystemTime;
 
-use crate::CanonicalRequest;
-use crate::CredentialScope;
-use crate::TimestampExt;
+use crate::canonical_request::CanonicalRequest;
+use crate::credential_scope::CredentialScope;
+use crate::datetime::TimestampExt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct StringToSign<'a> {
@@ -30,8 +30,6 @@ impl core::fmt::Display for StringToSign<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::hostname::Hostname;
-    use crate::hostname::InferredHostname;
 
     #[test]
     fn test_to_string() {
@@ -43,7 +41,7 @@ mod tests {
             },
             canonical_request: CanonicalRequest {
                 repo: "my-repo",
-                hostname: &Hostname::Inferred(InferredHostname::new("us-east-1")),
+                hostname: "git-codecommit.us-east-1.amazonaws.com",
             },
         }
         .to_string();
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::de::Error as _;
+use tracing::debug;
+
+use crate::partition::Partition;
+
+const APP_DIR: &str = "git-remote-codecommit";
+const ALIASES_FILE: &str = "aliases.toml";
+
+/// Short names for repositories and regions, loaded from `aliases.toml`.
+///
+/// ```toml
+/// [repositories.settle]
+/// profile = "payments-prod"
+/// region = "eu-west-1"
+/// repository = "platform-payments-settlement-service-v2"
+/// account_id = "123456789012"
+///
+/// [regions]
+/// syd = "ap-southeast-2"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Aliases {
+    #[serde(default)]
+    repositories: BTreeMap<String, RepositoryAlias>,
+    #[serde(default, deserialize_with = "deserialize_region_aliases")]
+    regions: BTreeMap<String, String>,
+}
+
+/// Rejects region aliases that are regions themselves, which would otherwise
+/// silently redirect remotes that name the region.
+fn deserialize_region_aliases<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error> {
+    let regions = BTreeMap::<String, String>::deserialize(deserializer)?;
+    if let Some(alias) = regions
+        .keys()
+        .find(|alias| Partition::for_region(alias).is_some())
+    {
+        return Err(D::Error::custom(format!(
+            "region alias `{alias}` is a known region"
+        )));
+    }
+    Ok(regions)
+}
+
+/// The remote a repository alias expands to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepositoryAlias {
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub repository: String,
+    pub account_id: Option<String>,
+}
+
+impl Aliases {
+    /// The default location of the alias file,
+    /// `$XDG_CONFIG_HOME/git-remote-codecommit/aliases.toml` falling back to
+    /// `~/.config/git-remote-codecommit/aliases.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::home_dir().map(|home| home.join(".config")))?;
+        Some(config_dir.join(APP_DIR).join(ALIASES_FILE))
+    }
+
+    /// Loads the alias file at `path`. A missing file has no aliases.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                debug!(path = %path.display(), "no alias file");
+                return Ok(Self::default());
+            }
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("failed to read alias file {}", path.display()));
+            }
+        };
+
+        contents
+            .parse()
+            .with_context(|| format!("failed to parse alias file {}", path.display()))
+    }
+
+    pub fn repository(&self, alias: &str) -> Option<&RepositoryAlias> {
+        self.repositories.get(alias)
+    }
+
+    /// The region `alias` stands for.
+    pub fn region(&self, alias: &str) -> Option<&str> {
+        self.regions.get(alias).map(String::as_str)
+    }
+
+    /// The names of every repository alias, in sorted order.
+    pub fn repository_names(&self) -> impl Iterator<Item = &str> {
+        self.repositories.keys().map(String::as_str)
+    }
+}
+
+impl core::str::FromStr for Aliases {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let aliases: Aliases = r#"
+            [repositories.settle]
+            profile = "payments-prod"
+            region = "eu-west-1"
+            repository = "platform-payments-settlement-service-v2"
+            account_id = "123456789012"
+
+            [repositories.docs]
+            repository = "docs"
+
+            [regions]
+            syd = "ap-southeast-2"
+        "#
+        .parse()
+        .expect("valid aliases");
+
+        assert_eq!(
+            Some(&RepositoryAlias {
+                profile: Some("payments-prod".to_owned()),
+                region: Some("eu-west-1".to_owned()),
+                repository: "platform-payments-settlement-service-v2".to_owned(),
+                account_id: Some("123456789012".to_owned()),
+            }),
+            aliases.repository("settle")
+        );
+        assert_eq!(
+            Some(&RepositoryAlias {
+                profile: None,
+                region: None,
+                repository: "docs".to_owned(),
+                account_id: None,
+            }),
+            aliases.repository("docs")
+        );
+        assert_eq!(None, aliases.repository("missing"));
+        assert_eq!(Some("ap-southeast-2"), aliases.region("syd"));
+        assert_eq!(None, aliases.region("settle"));
+        assert_eq!(
+            vec!["docs", "settle"],
+            aliases.repository_names().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let aliases: Aliases = "".parse().expect("valid aliases");
+        assert_eq!(Aliases::default(), aliases);
+    }
+
+    #[test]
+    fn test_parse_unknown_field() {
+        assert!(
+            "[repositories.settle]\nrepository = \"a\"\nbranch = \"main\"\n"
+                .parse::<Aliases>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_region_alias_is_region() {
+        let err = "[regions]\nus-east-1 = \"us-west-2\"\n"
+            .parse::<Aliases>()
+            .expect_err("region alias shadows a region");
+        assert!(
+            err.to_string()
+                .contains("region alias `us-east-1` is a known region"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_repository() {
+        assert!(
+            "[repositories.settle]\nprofile = \"a\"\n"
+                .parse::<Aliases>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = std::env::temp_dir().join(format!(
+            "git-remote-codecommit-{}-missing-aliases.toml",
+            std::process::id()
+        ));
+        let aliases = Aliases::load(&path).expect("missing file is empty");
+        assert_eq!(Aliases::default(), aliases);
+    }
+}
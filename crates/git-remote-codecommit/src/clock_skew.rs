@@ -0,0 +1,127 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
+use aws_smithy_types::DateTime;
+use aws_smithy_types::date_time::Format;
+use tracing::debug;
+
+/// How far the local clock may be from the server's before requests are
+/// signed with the server's time instead. `CodeCommit` rejects signatures more
+/// than a few minutes off; this leaves room for the one-second resolution of
+/// the `Date` header and the time git takes to send the first request.
+const MAX_SKEW: Duration = Duration::from_mins(1);
+
+/// How far the local clock is from the server's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Skew {
+    Ahead(Duration),
+    Behind(Duration),
+}
+
+impl Skew {
+    /// The skew of `local` from `server`, or `None` if it is within
+    /// [`MAX_SKEW`].
+    fn between(local: SystemTime, server: SystemTime) -> Option<Self> {
+        let skew = match local.duration_since(server) {
+            Ok(ahead) => Self::Ahead(ahead),
+            Err(err) => Self::Behind(err.duration()),
+        };
+        (skew.duration() > MAX_SKEW).then_some(skew)
+    }
+
+    fn duration(self) -> Duration {
+        match self {
+            Self::Ahead(duration) | Self::Behind(duration) => duration,
+        }
+    }
+
+    /// The server's time when the local clock reads `local`.
+    pub fn correct(self, local: SystemTime) -> SystemTime {
+        match self {
+            Self::Ahead(skew) => local - skew,
+            Self::Behind(skew) => local + skew,
+        }
+    }
+}
+
+impl core::fmt::Display for Skew {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Ahead(skew) => write!(f, "{} seconds ahead of", skew.as_secs()),
+            Self::Behind(skew) => write!(f, "{} seconds behind", skew.as_secs()),
+        }
+    }
+}
+
+/// Compares the local clock with `date`, the `Date` header of a response the
+/// server just sent.
+///
+/// Returns `None` if the clocks agree, or if the header is invalid, in which
+/// case git reports whatever went wrong when it connects.
+pub fn measure(date: &str) -> Option<Skew> {
+    let local = SystemTime::now();
+    let Some(server) = parse_http_date(date) else {
+        debug!(date, "invalid date header");
+        return None;
+    };
+    debug!(?local, ?server, "compared clocks");
+
+    Skew::between(local, server)
+}
+
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    DateTime::from_str(value, Format::HttpDate)
+        .ok()
+        .and_then(|date| SystemTime::try_from(date).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_date() {
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_576_540_098)),
+            parse_http_date("Mon, 16 Dec 2019 23:48:18 GMT")
+        );
+        assert_eq!(None, parse_http_date("2019-12-16T23:48:18Z"));
+    }
+
+    #[test]
+    fn test_measure() {
+        assert!(matches!(
+            measure("Mon, 16 Dec 2019 23:48:18 GMT"),
+            Some(Skew::Ahead(_))
+        ));
+        assert_eq!(None, measure("not a date"));
+    }
+
+    #[test]
+    fn test_skew_within_threshold() {
+        let server = SystemTime::UNIX_EPOCH + Duration::from_hours(1);
+        assert_eq!(None, Skew::between(server, server));
+        assert_eq!(None, Skew::between(server + MAX_SKEW, server));
+        assert_eq!(None, Skew::between(server - MAX_SKEW, server));
+    }
+
+    #[test]
+    fn test_skew_ahead() {
+        let server = SystemTime::UNIX_EPOCH + Duration::from_hours(1);
+        let local = server + Duration::from_secs(312);
+        let skew = Skew::between(local, server).expect("skewed");
+        assert_eq!(Skew::Ahead(Duration::from_secs(312)), skew);
+        assert_eq!("312 seconds ahead of", skew.to_string());
+        assert_eq!(server, skew.correct(local));
+    }
+
+    #[test]
+    fn test_skew_behind() {
+        let server = SystemTime::UNIX_EPOCH + Duration::from_hours(1);
+        let local = server - Duration::from_mins(10);
+        let skew = Skew::between(local, server).expect("skewed");
+        assert_eq!(Skew::Behind(Duration::from_mins(10)), skew);
+        assert_eq!("600 seconds behind", skew.to_string());
+        assert_eq!(server, skew.correct(local));
+    }
+}
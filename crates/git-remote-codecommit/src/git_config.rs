@@ -0,0 +1,389 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context;
+use tracing::debug;
+
+use crate::hostname::CliHostname;
+use crate::uri::ParseOptions;
+use crate::uri::ParsedUri;
+use crate::uri::RemoteOptions;
+
+const REPOSITORY_KEY: &str = "codecommitRepository";
+const REGION_KEY: &str = "codecommitRegion";
+const PROFILE_KEY: &str = "codecommitProfile";
+const ENDPOINT_KEY: &str = "codecommitEndpoint";
+
+/// The endpoint for remotes that do not set `remote.<name>.codecommitEndpoint`.
+const GLOBAL_ENDPOINT_KEY: &str = "codecommit.endpoint";
+
+/// Git config keys under `remote.<name>` that map onto a [`RemoteOptions`]
+/// query parameter.
+const OPTION_KEYS: &[(&str, &str)] = &[
+    ("codecommitRole", "role"),
+    ("codecommitAccountId", "account"),
+    ("codecommitSigningHost", "signinghost"),
+];
+
+/// Git config keys under `remote.<name>` that map onto a boolean
+/// [`RemoteOptions`] query parameter. Git reads these as booleans, so any
+/// spelling git accepts, e.g. `yes` or `on`, works.
+const BOOL_OPTION_KEYS: &[(&str, &str)] = &[
+    ("codecommitFips", "fips"),
+    ("codecommitDualstack", "dualstack"),
+];
+
+/// Reads values from git config by shelling out to `git config`.
+#[derive(Debug, Clone, Default)]
+pub struct GitConfig {
+    file: Option<PathBuf>,
+}
+
+impl GitConfig {
+    /// Reads the config of the repository git invoked the helper in.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads only the given config file.
+    #[cfg(test)]
+    pub fn from_file(file: impl Into<PathBuf>) -> Self {
+        Self {
+            file: Some(file.into()),
+        }
+    }
+
+    /// Returns the value of `key`, or `None` if it is not set.
+    pub fn get(&self, key: &str) -> anyhow::Result<Option<String>> {
+        self.get_as(key, None)
+    }
+
+    /// Returns the value of `key` read as a boolean, or `None` if it is not
+    /// set.
+    pub fn get_bool(&self, key: &str) -> anyhow::Result<Option<bool>> {
+        self.get_as(key, Some("bool"))?
+            .map(|value| match value.as_str() {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => anyhow::bail!("git config value for `{key}` is not a boolean: {value}"),
+            })
+            .transpose()
+    }
+
+    /// Returns the value of `key` canonicalized as `--type` for `ty`.
+    fn get_as(&self, key: &str, ty: Option<&str>) -> anyhow::Result<Option<String>> {
+        let mut command = Command::new("git");
+        command.arg("config");
+        if let Some(file) = &self.file {
+            command.arg("--file").arg(file);
+        }
+        if let Some(ty) = ty {
+            command.arg(format!("--type={ty}"));
+        }
+        command.arg("--get").arg(key);
+
+        let output = command
+            .output()
+            .with_context(|| format!("failed to run git config for `{key}`"))?;
+
+        match output.status.code() {
+            Some(0) => {
+                let value = String::from_utf8(output.stdout)
+                    .with_context(|| format!("git config value for `{key}` is not UTF-8"))?;
+                let value = value.trim_end_matches(['\r', '\n']).to_owned();
+                debug!(key, value, "read git config");
+                Ok(Some(value))
+            }
+            // `git config --get` exits with 1 when the key is not set
+            Some(1) => Ok(None),
+            _ => anyhow::bail!(
+                "git config for `{key}` failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        }
+    }
+
+    /// Returns the value of `remote.<remote_name>.<key>`.
+    pub fn get_remote(&self, remote_name: &str, key: &str) -> anyhow::Result<Option<String>> {
+        self.get(&format!("remote.{remote_name}.{key}"))
+    }
+
+    /// Returns the endpoint from `remote.<remote_name>.codecommitEndpoint`,
+    /// falling back to `codecommit.endpoint`. Without a remote name, only the
+    /// latter is read.
+    pub fn endpoint(&self, remote_name: Option<&str>) -> anyhow::Result<Option<CliHostname>> {
+        let key = match remote_name {
+            Some(remote_name) => format!("remote.{remote_name}.{ENDPOINT_KEY}"),
+            None => GLOBAL_ENDPOINT_KEY.to_owned(),
+        };
+
+        let value = match self.get(&key)? {
+            Some(value) => value,
+            None if remote_name.is_some() => return self.endpoint(None),
+            None => return Ok(None),
+        };
+
+        value
+            .parse()
+            .map(Some)
+            .map_err(anyhow::Error::msg)
+            .with_context(|| format!("invalid `{key}`"))
+    }
+
+    /// Builds the remote from `remote.<remote_name>.codecommit*` keys. Used
+    /// when git invokes the helper without a URL because the remote is
+    /// configured with `remote.<name>.vcs = codecommit`.
+    pub fn parsed_uri(
+        &self,
+        remote_name: &str,
+        parse_options: &ParseOptions,
+    ) -> anyhow::Result<ParsedUri> {
+        let repository = self
+            .get_remote(remote_name, REPOSITORY_KEY)?
+            .with_context(|| format!("`remote.{remote_name}.{REPOSITORY_KEY}` is not set"))?;
+        let region = self.get_remote(remote_name, REGION_KEY)?;
+        let profile = self.get_remote(remote_name, PROFILE_KEY)?;
+
+        let mut options = RemoteOptions::default();
+        for &(key, name) in OPTION_KEYS {
+            if let Some(value) = self.get_remote(remote_name, key)? {
+                options
+                    .insert(name, Some(&value))
+                    .with_context(|| format!("invalid `remote.{remote_name}.{key}`"))?;
+            }
+        }
+        for &(key, name) in BOOL_OPTION_KEYS {
+            let key = format!("remote.{remote_name}.{key}");
+            if let Some(value) = self
+                .get_bool(&key)
+                .with_context(|| format!("invalid `{key}`"))?
+            {
+                options
+                    .insert(name, Some(&value.to_string()))
+                    .with_context(|| format!("invalid `{key}`"))?;
+            }
+        }
+
+        ParsedUri::from_parts(region, profile, repository, options, parse_options)
+            .with_context(|| format!("invalid codecommit settings for remote `{remote_name}`"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    struct TempConfig(PathBuf);
+
+    impl TempConfig {
+        fn new(name: &str, contents: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "git-remote-codecommit-{}-{name}.gitconfig",
+                std::process::id()
+            ));
+            std::fs::write(&path, contents).expect("failed to write temp config");
+            Self(path)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            drop(std::fs::remove_file(&self.0));
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        let file = TempConfig::new("get", "[core]\n\tbare = false\n");
+        let config = GitConfig::from_file(file.path());
+        assert_eq!(
+            Some("false".to_owned()),
+            config.get("core.bare").expect("git config")
+        );
+        assert_eq!(None, config.get("core.missing").expect("git config"));
+    }
+
+    #[test]
+    fn test_parsed_uri() {
+        let file = TempConfig::new(
+            "parsed-uri",
+            "[remote \"origin\"]\n\
+             \tvcs = codecommit\n\
+             \tcodecommitRepository = my-repo\n\
+             \tcodecommitRegion = eu-west-1\n\
+             \tcodecommitProfile = team/prod\n\
+             \tcodecommitRole = arn:aws:iam::123456789012:role/git\n\
+             \tcodecommitAccountId = 123456789012\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        let parsed_uri = config
+            .parsed_uri("origin", &ParseOptions::default())
+            .expect("valid remote");
+        assert_eq!(Some("eu-west-1"), parsed_uri.region());
+        assert_eq!(Some("team/prod"), parsed_uri.profile());
+        assert_eq!("my-repo", parsed_uri.repository());
+        assert_eq!(
+            Some("arn:aws:iam::123456789012:role/git"),
+            parsed_uri.options().role_arn()
+        );
+        assert_eq!(Some("123456789012"), parsed_uri.account_id());
+    }
+
+    #[test]
+    fn test_parsed_uri_fips() {
+        for (value, expected) in [("yes", true), ("on", true), ("1", true), ("off", false)] {
+            let file = TempConfig::new(
+                &format!("fips-{value}"),
+                &format!(
+                    "[remote \"origin\"]\n\
+                     \tcodecommitRepository = my-repo\n\
+                     \tcodecommitFips = {value}\n"
+                ),
+            );
+            let config = GitConfig::from_file(file.path());
+            let parsed_uri = config
+                .parsed_uri("origin", &ParseOptions::default())
+                .expect("valid remote");
+            assert_eq!(Some(expected), parsed_uri.options().use_fips(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_parsed_uri_dual_stack() {
+        let file = TempConfig::new(
+            "dual-stack",
+            "[remote \"origin\"]\n\
+             \tcodecommitRepository = my-repo\n\
+             \tcodecommitDualstack\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        let parsed_uri = config
+            .parsed_uri("origin", &ParseOptions::default())
+            .expect("valid remote");
+        assert_eq!(Some(true), parsed_uri.options().use_dual_stack());
+    }
+
+    #[test]
+    fn test_parsed_uri_invalid_fips() {
+        let file = TempConfig::new(
+            "invalid-fips",
+            "[remote \"origin\"]\n\
+             \tcodecommitRepository = my-repo\n\
+             \tcodecommitFips = maybe\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        let err = config
+            .parsed_uri("origin", &ParseOptions::default())
+            .expect_err("invalid fips");
+        assert_eq!("invalid `remote.origin.codecommitFips`", err.to_string());
+    }
+
+    #[test]
+    fn test_parsed_uri_repository_only() {
+        let file = TempConfig::new(
+            "repository-only",
+            "[remote \"origin\"]\n\tcodecommitRepository = my-repo\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        let parsed_uri = config
+            .parsed_uri("origin", &ParseOptions::default())
+            .expect("valid remote");
+        assert_eq!(None, parsed_uri.region());
+        assert_eq!(None, parsed_uri.profile());
+        assert_eq!("my-repo", parsed_uri.repository());
+    }
+
+    #[test]
+    fn test_parsed_uri_missing_repository() {
+        let file = TempConfig::new(
+            "missing-repository",
+            "[remote \"origin\"]\n\tvcs = codecommit\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        let err = config
+            .parsed_uri("origin", &ParseOptions::default())
+            .expect_err("missing repository");
+        assert_eq!(
+            "`remote.origin.codecommitRepository` is not set",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let file = TempConfig::new(
+            "endpoint",
+            "[codecommit]\n\
+             \tendpoint = git-codecommit.us-east-1.amazonaws.com\n\
+             [remote \"vpce\"]\n\
+             \tcodecommitEndpoint = vpce-0abc-xyz.git-codecommit.us-east-1.vpce.amazonaws.com\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        assert_eq!(
+            Some("vpce-0abc-xyz.git-codecommit.us-east-1.vpce.amazonaws.com".to_owned()),
+            config
+                .endpoint(Some("vpce"))
+                .expect("valid endpoint")
+                .map(|endpoint| endpoint.to_string())
+        );
+        assert_eq!(
+            Some("git-codecommit.us-east-1.amazonaws.com".to_owned()),
+            config
+                .endpoint(Some("origin"))
+                .expect("valid endpoint")
+                .map(|endpoint| endpoint.to_string())
+        );
+        assert_eq!(
+            Some("git-codecommit.us-east-1.amazonaws.com".to_owned()),
+            config
+                .endpoint(None)
+                .expect("valid endpoint")
+                .map(|endpoint| endpoint.to_string())
+        );
+    }
+
+    #[test]
+    fn test_endpoint_unset() {
+        let file = TempConfig::new("endpoint-unset", "[core]\n\tbare = false\n");
+        let config = GitConfig::from_file(file.path());
+        assert_eq!(None, config.endpoint(Some("origin")).expect("no endpoint"));
+    }
+
+    #[test]
+    fn test_endpoint_invalid() {
+        let file = TempConfig::new(
+            "endpoint-invalid",
+            "[remote \"origin\"]\n\tcodecommitEndpoint = -bad-\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        let err = config
+            .endpoint(Some("origin"))
+            .expect_err("invalid endpoint");
+        assert_eq!(
+            "invalid `remote.origin.codecommitEndpoint`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parsed_uri_invalid_region() {
+        let file = TempConfig::new(
+            "invalid-region",
+            "[remote \"origin\"]\n\
+             \tcodecommitRepository = my-repo\n\
+             \tcodecommitRegion = us-east1\n",
+        );
+        let config = GitConfig::from_file(file.path());
+        assert!(
+            config
+                .parsed_uri("origin", &ParseOptions::default())
+                .is_err()
+        );
+    }
+}
@@ -1,55 +1,287 @@
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
 use std::num::NonZeroU16;
 
+use crate::partition::PARTITIONS;
+use crate::partition::Partition;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct InferredHostname<'a> {
     region: &'a str,
+    fips: bool,
+    dual_stack: bool,
+}
+
+/// The scheme git connects to the endpoint with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    #[default]
+    Https,
+    /// Plain HTTP, which exposes the signed URL to anyone on the network.
+    Http,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CliHostname {
+    /// The scheme, if one was given explicitly.
+    scheme: Option<Scheme>,
+    /// A DNS name, an `IPv4` literal or a bracketed `IPv6` literal.
     host: String,
     port: Option<NonZeroU16>,
 }
 
+/// A hostname with `{region}`, `{partition_suffix}` and `{service}`
+/// placeholders, e.g. `codecommit-{region}.git.corp.example`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostnameTemplate {
+    scheme: Option<Scheme>,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Segment {
+    Literal(String),
+    Region,
+    PartitionSuffix,
+    Service,
+}
+
+/// A [`HostnameTemplate`] expanded for one region.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TemplatedHostname<'a> {
+    template: &'a HostnameTemplate,
+    region: &'a str,
+    dual_stack: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Hostname<'a> {
     Inferred(InferredHostname<'a>),
     Cli(CliHostname),
+    Template(TemplatedHostname<'a>),
+}
+
+/// The host to connect to and the host to put in the signature.
+///
+/// These are the same unless the remote is reached through a name the
+/// service does not sign for, such as an interface VPC endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Endpoint<'a> {
+    pub connect: Hostname<'a>,
+    pub signing: Hostname<'a>,
+}
+
+impl<'a> Endpoint<'a> {
+    /// Connects to and signs for the same host.
+    pub fn new(hostname: Hostname<'a>) -> Self {
+        Self {
+            connect: hostname.clone(),
+            signing: hostname,
+        }
+    }
+
+    /// Signs for `signing` instead of the connect host.
+    pub fn with_signing_host(self, signing: Hostname<'a>) -> Self {
+        Self { signing, ..self }
+    }
+}
+
+impl Hostname<'_> {
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            Hostname::Inferred(_) => Scheme::Https,
+            Hostname::Cli(cli) => cli.scheme(),
+            Hostname::Template(templated) => templated.template.scheme(),
+        }
+    }
 }
 
 impl<'a> InferredHostname<'a> {
     pub fn new(region: &'a str) -> Self {
-        Self { region }
+        Self {
+            region,
+            fips: false,
+            dual_stack: false,
+        }
+    }
+
+    /// Use the FIPS endpoint of the region.
+    pub fn with_fips(self, fips: bool) -> Self {
+        Self { fips, ..self }
+    }
+
+    /// Use the dual-stack endpoint of the region.
+    pub fn with_dual_stack(self, dual_stack: bool) -> Self {
+        Self { dual_stack, ..self }
     }
 }
 
 impl core::fmt::Display for InferredHostname<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let &Self { region } = self;
-        let partition = if region.starts_with("cn-") {
-            "amazonaws.com.cn"
+        let &Self {
+            region,
+            fips,
+            dual_stack,
+        } = self;
+        let partition = Partition::resolve(region);
+        let dns_suffix = if dual_stack {
+            partition.dual_stack_dns_suffix()
         } else {
-            "amazonaws.com"
+            partition.dns_suffix()
         };
-        write!(f, "git-codecommit.{region}.{partition}")
+        let fips = if fips { "-fips" } else { "" };
+        write!(f, "git-codecommit{fips}.{region}.{dns_suffix}")
+    }
+}
+
+impl HostnameTemplate {
+    /// Expands the template for `region`.
+    pub fn expand<'a>(&'a self, region: &'a str) -> TemplatedHostname<'a> {
+        TemplatedHostname {
+            template: self,
+            region,
+            dual_stack: false,
+        }
+    }
+
+    /// The scheme to connect with, `https` unless given otherwise.
+    pub fn scheme(&self) -> Scheme {
+        self.scheme.unwrap_or_default()
+    }
+}
+
+impl TemplatedHostname<'_> {
+    /// Expand `{partition_suffix}` to the dual-stack suffix of the partition.
+    pub fn with_dual_stack(self, dual_stack: bool) -> Self {
+        Self { dual_stack, ..self }
+    }
+}
+
+impl core::fmt::Display for TemplatedHostname<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let &Self {
+            template,
+            region,
+            dual_stack,
+        } = self;
+        let partition = Partition::resolve(region);
+        for segment in &template.segments {
+            match segment {
+                Segment::Literal(literal) => f.write_str(literal)?,
+                Segment::Region => f.write_str(region)?,
+                Segment::PartitionSuffix if dual_stack => {
+                    f.write_str(partition.dual_stack_dns_suffix())?;
+                }
+                Segment::PartitionSuffix => f.write_str(partition.dns_suffix())?,
+                Segment::Service => f.write_str(crate::SERVICE)?,
+            }
+        }
+        Ok(())
     }
 }
 
+/// Writes the template as it was given.
+impl core::fmt::Display for HostnameTemplate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(scheme) = self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => f.write_str(literal)?,
+                Segment::Region => f.write_str(REGION_PLACEHOLDER)?,
+                Segment::PartitionSuffix => f.write_str(PARTITION_SUFFIX_PLACEHOLDER)?,
+                Segment::Service => f.write_str(SERVICE_PLACEHOLDER)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for Scheme {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Scheme::Https => HTTPS,
+            Scheme::Http => HTTP,
+        })
+    }
+}
+
+/// Writes the scheme only if it was given, so that the value round-trips.
 impl core::fmt::Display for CliHostname {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        if let Some(port) = self.port {
-            write!(f, "{}:{}", self.host, port)
-        } else {
-            write!(f, "{}", self.host)
+        if let Some(scheme) = self.scheme {
+            write!(f, "{scheme}://")?;
         }
+        self.fmt_authority(f)
     }
 }
 
+/// Writes the `host[:port]` that goes in the URL and the signature; the
+/// scheme is available from [`Hostname::scheme`].
 impl core::fmt::Display for Hostname<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Hostname::Inferred(inferred) => inferred.fmt(f),
-            Hostname::Cli(cli) => cli.fmt(f),
+            Hostname::Cli(cli) => cli.fmt_authority(f),
+            Hostname::Template(templated) => templated.fmt(f),
+        }
+    }
+}
+
+const HTTPS: &str = "https";
+const HTTP: &str = "http";
+
+const REGION_PLACEHOLDER: &str = "{region}";
+const PARTITION_SUFFIX_PLACEHOLDER: &str = "{partition_suffix}";
+const SERVICE_PLACEHOLDER: &str = "{service}";
+
+impl CliHostname {
+    /// Parses an endpoint URL as found in the AWS config, e.g.
+    /// `http://localhost:4566/`. Only URLs without a path are accepted.
+    pub fn from_endpoint_url(url: &str) -> Result<Self, &'static str> {
+        let url = url.strip_suffix('/').unwrap_or(url);
+        url.contains("://")
+            .then_some(url)
+            .and_then(|url| url.parse().ok())
+            .ok_or("invalid endpoint URL")
+    }
+
+    /// Parses a host to sign for, e.g.
+    /// `git-codecommit.us-east-1.amazonaws.com`. A scheme is rejected
+    /// rather than ignored, as nothing is connected to.
+    pub fn from_signing_host(value: &str) -> Result<Self, &'static str> {
+        let hostname: Self = value.parse()?;
+        hostname
+            .scheme
+            .is_none()
+            .then_some(hostname)
+            .ok_or("the signing host takes no scheme")
+    }
+
+    /// The scheme to connect with, `https` unless given otherwise.
+    pub fn scheme(&self) -> Scheme {
+        self.scheme.unwrap_or_default()
+    }
+
+    fn fmt_authority(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(port) = self.port {
+            write!(f, "{}:{}", self.host, port)
+        } else {
+            write!(f, "{}", self.host)
+        }
+    }
+}
+
+impl core::str::FromStr for Scheme {
+    type Err = &'static str;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case(HTTPS) {
+            Ok(Scheme::Https)
+        } else if value.eq_ignore_ascii_case(HTTP) {
+            Ok(Scheme::Http)
+        } else {
+            Err("unsupported scheme")
         }
     }
 }
@@ -57,25 +289,103 @@ impl core::fmt::Display for Hostname<'_> {
 impl core::str::FromStr for CliHostname {
     type Err = &'static str;
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let (host, port) = extract_hostname(value).ok_or("invalid hostname")?;
+        let (scheme, authority) = match value.split_once("://") {
+            Some((scheme, authority)) => (Some(scheme.parse()?), authority),
+            None => (None, value),
+        };
+        let (host, port) = extract_ipv6_literal(authority)
+            .or_else(|| extract_hostname(authority))
+            .ok_or("invalid hostname")?;
+        if is_dotted_quad(host) {
+            host.parse::<Ipv4Addr>()
+                .map_err(|_| "invalid IPv4 address")?;
+        }
         let host = host.to_owned();
-        Ok(Self { host, port })
+        Ok(Self { scheme, host, port })
+    }
+}
+
+impl core::str::FromStr for HostnameTemplate {
+    type Err = &'static str;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (scheme, mut rest) = match value.split_once("://") {
+            Some((scheme, rest)) => (Some(scheme.parse()?), rest),
+            None => (None, value),
+        };
+
+        let mut segments = Vec::new();
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_owned()));
+            }
+            let len = rest[start..].find('}').ok_or("unclosed placeholder")? + 1;
+            segments.push(match &rest[start..start + len] {
+                REGION_PLACEHOLDER => Segment::Region,
+                PARTITION_SUFFIX_PLACEHOLDER => Segment::PartitionSuffix,
+                SERVICE_PLACEHOLDER => Segment::Service,
+                _ => return Err("unknown placeholder"),
+            });
+            rest = &rest[start + len..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_owned()));
+        }
+
+        // Check every known region, as the partition suffix differs between
+        // partitions and with dual-stack endpoints.
+        let template = Self { scheme, segments };
+        let regions = PARTITIONS.iter().flat_map(Partition::regions);
+        for (region, dual_stack) in regions.flat_map(|region| [(region, false), (region, true)]) {
+            template
+                .expand(region)
+                .with_dual_stack(dual_stack)
+                .to_string()
+                .parse::<CliHostname>()
+                .map_err(|_| "template does not expand to a valid hostname")?;
+        }
+        Ok(template)
     }
 }
 
 #[inline]
-fn extract_hostname(hostname: &str) -> Option<(&str, Option<NonZeroU16>)> {
+fn parse_port(mut bytes: std::iter::Copied<std::slice::Iter<'_, u8>>) -> Option<NonZeroU16> {
     #[inline]
-    fn parse_port(mut bytes: std::iter::Copied<std::slice::Iter<'_, u8>>) -> Option<NonZeroU16> {
-        #[inline]
-        fn add_digit(port: u16, digit: u8) -> Option<u16> {
-            let digit = digit.is_ascii_digit().then_some(digit)?;
-            let port = port.checked_mul(10)?;
-            port.checked_add(u16::from(digit - b'0'))
-        }
-        bytes.try_fold(0_u16, add_digit).and_then(NonZeroU16::new)
+    fn add_digit(port: u16, digit: u8) -> Option<u16> {
+        let digit = digit.is_ascii_digit().then_some(digit)?;
+        let port = port.checked_mul(10)?;
+        port.checked_add(u16::from(digit - b'0'))
     }
+    bytes.try_fold(0_u16, add_digit).and_then(NonZeroU16::new)
+}
+
+/// Extracts a bracketed `IPv6` literal, e.g. `[::1]:8443`, keeping the brackets
+/// as they appear in the URL and the `host` header.
+///
+/// `IPv4` literals need no special handling as they are valid hostnames.
+#[inline]
+fn extract_ipv6_literal(hostname: &str) -> Option<(&str, Option<NonZeroU16>)> {
+    let end = hostname.strip_prefix('[')?.find(']')? + 2;
+    let (host, rest) = hostname.split_at(end);
+    host[1..end - 1].parse::<Ipv6Addr>().ok()?;
+    let port = match rest.strip_prefix(':') {
+        Some(port) => Some(parse_port(port.as_bytes().iter().copied())?),
+        None if rest.is_empty() => None,
+        None => return None,
+    };
+    Some((host, port))
+}
+
+/// Whether `host` has four numeric labels, in which case it is an `IPv4`
+/// literal rather than a DNS name.
+fn is_dotted_quad(host: &str) -> bool {
+    host.split('.').count() == 4
+        && host
+            .split('.')
+            .all(|label| label.bytes().all(|b| b.is_ascii_digit()))
+}
 
+#[inline]
+fn extract_hostname(hostname: &str) -> Option<(&str, Option<NonZeroU16>)> {
     let mut bytes = hostname.as_bytes().iter().copied();
     let mut host_len = 0;
     let mut label_len = 0;
@@ -133,6 +443,154 @@ mod tests {
         assert_eq!(s, "git-codecommit.us-east-1.amazonaws.com");
     }
 
+    #[test]
+    fn test_every_partition() {
+        const CASES: &[(&str, &str)] = &[
+            ("eu-west-1", "git-codecommit.eu-west-1.amazonaws.com"),
+            (
+                "cn-northwest-1",
+                "git-codecommit.cn-northwest-1.amazonaws.com.cn",
+            ),
+            (
+                "us-gov-west-1",
+                "git-codecommit.us-gov-west-1.amazonaws.com",
+            ),
+            ("us-iso-east-1", "git-codecommit.us-iso-east-1.c2s.ic.gov"),
+            (
+                "us-isob-east-1",
+                "git-codecommit.us-isob-east-1.sc2s.sgov.gov",
+            ),
+            (
+                "eu-isoe-west-1",
+                "git-codecommit.eu-isoe-west-1.cloud.adc-e.uk",
+            ),
+            (
+                "us-isof-south-1",
+                "git-codecommit.us-isof-south-1.csp.hci.ic.gov",
+            ),
+            (
+                "eusc-de-east-1",
+                "git-codecommit.eusc-de-east-1.amazonaws.eu",
+            ),
+        ];
+
+        for &(region, expected) in CASES {
+            assert_eq!(InferredHostname::new(region).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_unknown_region() {
+        let s = InferredHostname::new("us-isob-west-1").to_string();
+        assert_eq!(s, "git-codecommit.us-isob-west-1.sc2s.sgov.gov");
+    }
+
+    #[test]
+    fn test_fips() {
+        let s = InferredHostname::new("us-east-1")
+            .with_fips(true)
+            .to_string();
+        assert_eq!(s, "git-codecommit-fips.us-east-1.amazonaws.com");
+    }
+
+    #[test]
+    fn test_fips_gov() {
+        let s = InferredHostname::new("us-gov-west-1")
+            .with_fips(true)
+            .to_string();
+        assert_eq!(s, "git-codecommit-fips.us-gov-west-1.amazonaws.com");
+    }
+
+    #[test]
+    fn test_dual_stack() {
+        let s = InferredHostname::new("us-east-1")
+            .with_dual_stack(true)
+            .to_string();
+        assert_eq!(s, "git-codecommit.us-east-1.api.aws");
+    }
+
+    #[test]
+    fn test_dual_stack_cn() {
+        let s = InferredHostname::new("cn-north-1")
+            .with_dual_stack(true)
+            .to_string();
+        assert_eq!(s, "git-codecommit.cn-north-1.api.amazonwebservices.com.cn");
+    }
+
+    #[test]
+    fn test_fips_dual_stack() {
+        let s = InferredHostname::new("us-gov-west-1")
+            .with_fips(true)
+            .with_dual_stack(true)
+            .to_string();
+        assert_eq!(s, "git-codecommit-fips.us-gov-west-1.api.aws");
+    }
+
+    // HostnameTemplate
+
+    #[test]
+    fn test_template() {
+        let template: HostnameTemplate = "codecommit-{region}.git.corp.example".parse().unwrap();
+        assert_eq!(
+            template.expand("eu-west-1").to_string(),
+            "codecommit-eu-west-1.git.corp.example"
+        );
+        assert_eq!(
+            template.expand("us-east-2").to_string(),
+            "codecommit-us-east-2.git.corp.example"
+        );
+        assert_eq!(template.to_string(), "codecommit-{region}.git.corp.example");
+    }
+
+    #[test]
+    fn test_template_default_equivalent() {
+        let template: HostnameTemplate =
+            "git-{service}.{region}.{partition_suffix}".parse().unwrap();
+        for region in ["us-east-1", "cn-north-1", "us-iso-east-1"] {
+            assert_eq!(
+                template.expand(region).to_string(),
+                InferredHostname::new(region).to_string()
+            );
+            assert_eq!(
+                template.expand(region).with_dual_stack(true).to_string(),
+                InferredHostname::new(region)
+                    .with_dual_stack(true)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_template_scheme_and_port() {
+        let template: HostnameTemplate = "http://{region}.localhost:8080".parse().unwrap();
+        assert_eq!(template.scheme(), Scheme::Http);
+        let h = Hostname::Template(template.expand("us-west-2"));
+        assert_eq!(h.to_string(), "us-west-2.localhost:8080");
+        assert_eq!(h.scheme(), Scheme::Http);
+        assert_eq!(template.to_string(), "http://{region}.localhost:8080");
+    }
+
+    #[test]
+    fn test_template_invalid() {
+        assert!("{region".parse::<HostnameTemplate>().is_err());
+        assert!("{zone}.example".parse::<HostnameTemplate>().is_err());
+        assert!("{region}_git.example".parse::<HostnameTemplate>().is_err());
+        assert!("{region}}.example".parse::<HostnameTemplate>().is_err());
+        assert!("".parse::<HostnameTemplate>().is_err());
+    }
+
+    #[test]
+    fn test_template_invalid_for_other_regions() {
+        // A 63 character label for `us-east-1`, but too long for longer names.
+        let template = format!("{{region}}{}.example", "a".repeat(54));
+        assert!(
+            format!("us-east-1{}.example", "a".repeat(54))
+                .parse::<CliHostname>()
+                .is_ok()
+        );
+        assert!(template.parse::<HostnameTemplate>().is_err());
+    }
+
     // CliHostname::from_str — valid inputs
 
     #[test]
@@ -198,6 +656,42 @@ mod tests {
         assert_eq!(h.port, None);
     }
 
+    #[test]
+    fn test_cli_hostname_ipv4() {
+        let h: CliHostname = "127.0.0.1:8443".parse().unwrap();
+        assert_eq!(h.host, "127.0.0.1");
+        assert_eq!(h.port, Some(NonZeroU16::new(8443).unwrap()));
+    }
+
+    #[test]
+    fn test_cli_hostname_ipv6() {
+        let h: CliHostname = "[::1]".parse().unwrap();
+        assert_eq!(h.host, "[::1]");
+        assert_eq!(h.port, None);
+    }
+
+    #[test]
+    fn test_cli_hostname_ipv6_with_port() {
+        let h: CliHostname = "[2001:db8::1]:8443".parse().unwrap();
+        assert_eq!(h.host, "[2001:db8::1]");
+        assert_eq!(h.port, Some(NonZeroU16::new(8443).unwrap()));
+    }
+
+    #[test]
+    fn test_cli_hostname_scheme() {
+        let h: CliHostname = "localhost".parse().unwrap();
+        assert_eq!(h.scheme, None);
+        assert_eq!(h.scheme(), Scheme::Https);
+
+        let h: CliHostname = "https://localhost".parse().unwrap();
+        assert_eq!(h.scheme, Some(Scheme::Https));
+
+        let h: CliHostname = "http://[::1]:8080".parse().unwrap();
+        assert_eq!(h.scheme(), Scheme::Http);
+        assert_eq!(h.host, "[::1]");
+        assert_eq!(h.port, Some(NonZeroU16::new(8080).unwrap()));
+    }
+
     // CliHostname::from_str — invalid inputs
 
     #[test]
@@ -265,6 +759,32 @@ mod tests {
         assert!("host name".parse::<CliHostname>().is_err());
     }
 
+    #[test]
+    fn test_cli_hostname_unbracketed_ipv6() {
+        assert!("::1".parse::<CliHostname>().is_err());
+    }
+
+    #[test]
+    fn test_cli_hostname_invalid_ipv6() {
+        assert!("[::g]".parse::<CliHostname>().is_err());
+        assert!("[::1".parse::<CliHostname>().is_err());
+        assert!("[::1]8443".parse::<CliHostname>().is_err());
+        assert!("[::1]:".parse::<CliHostname>().is_err());
+    }
+
+    #[test]
+    fn test_cli_hostname_invalid_ipv4() {
+        assert!("999.1.1.1".parse::<CliHostname>().is_err());
+        assert!("256.0.0.1:8443".parse::<CliHostname>().is_err());
+        assert!("http://01.2.3.4".parse::<CliHostname>().is_err());
+    }
+
+    #[test]
+    fn test_cli_hostname_unsupported_scheme() {
+        assert!("ssh://localhost".parse::<CliHostname>().is_err());
+        assert!("://localhost".parse::<CliHostname>().is_err());
+    }
+
     #[test]
     fn test_cli_hostname_label_too_long() {
         let label = "a".repeat(64);
@@ -280,6 +800,53 @@ mod tests {
         assert!(hostname.parse::<CliHostname>().is_err());
     }
 
+    // CliHostname::from_endpoint_url
+
+    #[test]
+    fn test_cli_hostname_from_endpoint_url() {
+        let h = CliHostname::from_endpoint_url("https://localhost:4566").unwrap();
+        assert_eq!(h.host, "localhost");
+        assert_eq!(h.port, Some(NonZeroU16::new(4566).unwrap()));
+    }
+
+    #[test]
+    fn test_cli_hostname_from_endpoint_url_trailing_slash() {
+        let h = CliHostname::from_endpoint_url("https://codecommit.example/").unwrap();
+        assert_eq!(h.host, "codecommit.example");
+        assert_eq!(h.port, None);
+    }
+
+    #[test]
+    fn test_cli_hostname_from_endpoint_url_http() {
+        let h = CliHostname::from_endpoint_url("http://127.0.0.1:4566").unwrap();
+        assert_eq!(h.scheme(), Scheme::Http);
+        assert_eq!(h.host, "127.0.0.1");
+    }
+
+    #[test]
+    fn test_cli_hostname_from_endpoint_url_invalid() {
+        assert!(CliHostname::from_endpoint_url("localhost:4566").is_err());
+        assert!(CliHostname::from_endpoint_url("https://localhost/v1").is_err());
+        assert!(CliHostname::from_endpoint_url("ftp://localhost").is_err());
+    }
+
+    // CliHostname::from_signing_host
+
+    #[test]
+    fn test_cli_hostname_from_signing_host() {
+        let h = CliHostname::from_signing_host("localhost:8443").unwrap();
+        assert_eq!(h.scheme, None);
+        assert_eq!(h.host, "localhost");
+        assert_eq!(h.port, Some(NonZeroU16::new(8443).unwrap()));
+    }
+
+    #[test]
+    fn test_cli_hostname_from_signing_host_scheme() {
+        assert!(CliHostname::from_signing_host("https://localhost").is_err());
+        assert!(CliHostname::from_signing_host("http://[::1]:8443").is_err());
+        assert!(CliHostname::from_signing_host("ssh://localhost").is_err());
+    }
+
     // CliHostname Display
 
     #[test]
@@ -294,6 +861,12 @@ mod tests {
         assert_eq!(h.to_string(), "host");
     }
 
+    #[test]
+    fn test_cli_hostname_display_with_scheme() {
+        let h: CliHostname = "http://[::1]:8080".parse().unwrap();
+        assert_eq!(h.to_string(), "http://[::1]:8080");
+    }
+
     // Hostname enum Display delegation
 
     #[test]
@@ -307,4 +880,11 @@ mod tests {
         let h = Hostname::Cli("example.com:8080".parse().unwrap());
         assert_eq!(h.to_string(), "example.com:8080");
     }
+
+    #[test]
+    fn test_hostname_display_cli_omits_scheme() {
+        let h = Hostname::Cli("http://example.com:8080".parse().unwrap());
+        assert_eq!(h.to_string(), "example.com:8080");
+        assert_eq!(h.scheme(), Scheme::Http);
+    }
 }
@@ -3,10 +3,16 @@ use std::io::IsTerminal;
 use tracing::Level;
 use tracing_subscriber::EnvFilter;
 
+/// Shows this crate's warnings, e.g. about failover, but only errors from the
+/// AWS SDK and HTTP libraries.
+const DEFAULT_DIRECTIVES: &str = "error,git_remote_codecommit=warn";
+
 pub fn init_logging() {
+    let directives =
+        std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_else(|_| DEFAULT_DIRECTIVES.to_owned());
     let filter = EnvFilter::builder()
         .with_default_directive(Level::ERROR.into())
-        .from_env_lossy();
+        .parse_lossy(directives);
     tracing_subscriber::fmt()
         .with_ansi(term_color())
         .with_writer(std::io::stderr)
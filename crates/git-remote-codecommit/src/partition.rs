@@ -0,0 +1,413 @@
+/// An AWS partition: the regions it is known to contain and the DNS suffix
+/// its endpoints live under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Partition {
+    id: &'static str,
+    dns_suffix: &'static str,
+    dual_stack_dns_suffix: &'static str,
+    /// Prefixes that identify regions of this partition that are not in
+    /// `regions`, e.g. regions that launched after this binary was built.
+    region_prefixes: &'static [&'static str],
+    regions: &'static [&'static str],
+}
+
+/// Every partition and region known when this binary was built.
+pub const PARTITIONS: &[Partition] = &[
+    Partition {
+        id: "aws",
+        dns_suffix: "amazonaws.com",
+        dual_stack_dns_suffix: "api.aws",
+        region_prefixes: &[
+            "af-", "ap-", "ca-", "eu-", "il-", "me-", "mx-", "sa-", "us-",
+        ],
+        regions: &[
+            "af-south-1",
+            "ap-east-1",
+            "ap-east-2",
+            "ap-northeast-1",
+            "ap-northeast-2",
+            "ap-northeast-3",
+            "ap-south-1",
+            "ap-south-2",
+            "ap-southeast-1",
+            "ap-southeast-2",
+            "ap-southeast-3",
+            "ap-southeast-4",
+            "ap-southeast-5",
+            "ap-southeast-6",
+            "ap-southeast-7",
+            "ca-central-1",
+            "ca-west-1",
+            "eu-central-1",
+            "eu-central-2",
+            "eu-north-1",
+            "eu-south-1",
+            "eu-south-2",
+            "eu-west-1",
+            "eu-west-2",
+            "eu-west-3",
+            "il-central-1",
+            "me-central-1",
+            "me-south-1",
+            "mx-central-1",
+            "sa-east-1",
+            "us-east-1",
+            "us-east-2",
+            "us-west-1",
+            "us-west-2",
+        ],
+    },
+    Partition {
+        id: "aws-cn",
+        dns_suffix: "amazonaws.com.cn",
+        dual_stack_dns_suffix: "api.amazonwebservices.com.cn",
+        region_prefixes: &["cn-"],
+        regions: &["cn-north-1", "cn-northwest-1"],
+    },
+    Partition {
+        id: "aws-us-gov",
+        dns_suffix: "amazonaws.com",
+        dual_stack_dns_suffix: "api.aws",
+        region_prefixes: &["us-gov-"],
+        regions: &["us-gov-east-1", "us-gov-west-1"],
+    },
+    Partition {
+        id: "aws-iso",
+        dns_suffix: "c2s.ic.gov",
+        dual_stack_dns_suffix: "api.aws.ic.gov",
+        region_prefixes: &["us-iso-"],
+        regions: &["us-iso-east-1", "us-iso-west-1"],
+    },
+    Partition {
+        id: "aws-iso-b",
+        dns_suffix: "sc2s.sgov.gov",
+        dual_stack_dns_suffix: "api.aws.scloud",
+        region_prefixes: &["us-isob-"],
+        regions: &["us-isob-east-1"],
+    },
+    Partition {
+        id: "aws-iso-e",
+        dns_suffix: "cloud.adc-e.uk",
+        dual_stack_dns_suffix: "api.cloud-aws.adc-e.uk",
+        region_prefixes: &["eu-isoe-"],
+        regions: &["eu-isoe-west-1"],
+    },
+    Partition {
+        id: "aws-iso-f",
+        dns_suffix: "csp.hci.ic.gov",
+        dual_stack_dns_suffix: "api.aws.hci.ic.gov",
+        region_prefixes: &["us-isof-"],
+        regions: &["us-isof-east-1", "us-isof-south-1"],
+    },
+    Partition {
+        id: "aws-eusc",
+        dns_suffix: "amazonaws.eu",
+        dual_stack_dns_suffix: "api.amazonwebservices.eu",
+        region_prefixes: &["eusc-"],
+        regions: &["eusc-de-east-1"],
+    },
+];
+
+/// The partition assumed for regions that match no other partition.
+const DEFAULT_PARTITION: &Partition = &PARTITIONS[0];
+
+impl Partition {
+    /// Finds the partition that is known to contain `region`.
+    pub fn for_region(region: &str) -> Option<&'static Self> {
+        PARTITIONS
+            .iter()
+            .find(|partition| partition.regions.contains(&region))
+    }
+
+    /// Finds the partition `region` belongs to, also for regions that are not
+    /// in the table.
+    ///
+    /// Unknown regions are matched by their prefix, preferring the longest,
+    /// so that `us-gov-` wins over `us-`. Regions that match no prefix are
+    /// assumed to be in the `aws` partition.
+    pub fn resolve(region: &str) -> &'static Self {
+        Self::for_region(region)
+            .or_else(|| {
+                PARTITIONS
+                    .iter()
+                    .flat_map(|partition| {
+                        partition
+                            .region_prefixes
+                            .iter()
+                            .map(move |prefix| (prefix, partition))
+                    })
+                    .filter(|(prefix, _)| region.starts_with(**prefix))
+                    .max_by_key(|(prefix, _)| prefix.len())
+                    .map(|(_, partition)| partition)
+            })
+            .unwrap_or(DEFAULT_PARTITION)
+    }
+
+    /// Finds the partition named `id`, e.g. `aws-cn`.
+    pub fn for_id(id: &str) -> Option<&'static Self> {
+        PARTITIONS.iter().find(|partition| partition.id == id)
+    }
+
+    #[cfg_attr(not(test), expect(dead_code))]
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    /// The domain endpoints of this partition live under, e.g.
+    /// `amazonaws.com.cn`.
+    pub fn dns_suffix(&self) -> &'static str {
+        self.dns_suffix
+    }
+
+    /// The domain dual-stack endpoints of this partition live under, e.g.
+    /// `api.aws`.
+    pub fn dual_stack_dns_suffix(&self) -> &'static str {
+        self.dual_stack_dns_suffix
+    }
+
+    pub fn regions(&self) -> &'static [&'static str] {
+        self.regions
+    }
+}
+
+/// Regions where `CodeCommit` has a `git-codecommit-fips` endpoint.
+const FIPS_REGIONS: &[&str] = &[
+    "ca-central-1",
+    "us-east-1",
+    "us-east-2",
+    "us-gov-east-1",
+    "us-gov-west-1",
+    "us-west-1",
+    "us-west-2",
+];
+
+/// Returns true if `CodeCommit` has a FIPS endpoint in `region`.
+pub fn has_fips_endpoint(region: &str) -> bool {
+    FIPS_REGIONS.contains(&region)
+}
+
+/// Short codes for regions, after the IATA code of a nearby airport.
+const REGION_CODES: &[(&str, &str)] = &[
+    ("akl", "ap-southeast-6"),
+    ("arn", "eu-north-1"),
+    ("bah", "me-south-1"),
+    ("bjs", "cn-north-1"),
+    ("bkk", "ap-southeast-7"),
+    ("bom", "ap-south-1"),
+    ("cdg", "eu-west-3"),
+    ("cgk", "ap-southeast-3"),
+    ("cmh", "us-east-2"),
+    ("cpt", "af-south-1"),
+    ("dub", "eu-west-1"),
+    ("dxb", "me-central-1"),
+    ("fra", "eu-central-1"),
+    ("gru", "sa-east-1"),
+    ("hkg", "ap-east-1"),
+    ("hyd", "ap-south-2"),
+    ("iad", "us-east-1"),
+    ("icn", "ap-northeast-2"),
+    ("kix", "ap-northeast-3"),
+    ("kul", "ap-southeast-5"),
+    ("lhr", "eu-west-2"),
+    ("mel", "ap-southeast-4"),
+    ("mxp", "eu-south-1"),
+    ("nrt", "ap-northeast-1"),
+    ("osu", "us-gov-east-1"),
+    ("pdt", "us-gov-west-1"),
+    ("pdx", "us-west-2"),
+    ("qro", "mx-central-1"),
+    ("sfo", "us-west-1"),
+    ("sin", "ap-southeast-1"),
+    ("syd", "ap-southeast-2"),
+    ("tlv", "il-central-1"),
+    ("tpe", "ap-east-2"),
+    ("yul", "ca-central-1"),
+    ("yyc", "ca-west-1"),
+    ("zaz", "eu-south-2"),
+    ("zhy", "cn-northwest-1"),
+    ("zrh", "eu-central-2"),
+];
+
+/// Returns the region a built-in short code such as `syd` stands for.
+pub fn region_for_code(code: &str) -> Option<&'static str> {
+    REGION_CODES
+        .iter()
+        .find(|&&(known, _)| known == code)
+        .map(|&(_, region)| region)
+}
+
+/// The largest edit distance at which a known region is still suggested.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// Finds the known region closest to `region` by edit distance.
+///
+/// Returns `None` if no region is close enough to be a plausible typo.
+pub fn closest_region(region: &str) -> Option<&'static str> {
+    PARTITIONS
+        .iter()
+        .flat_map(Partition::regions)
+        .map(|&known| (strsim::levenshtein(region, known), known))
+        .filter(|&(distance, _)| distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, known)| known)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_region() {
+        let partition = Partition::for_region("us-east-1").expect("known region");
+        assert_eq!("aws", partition.id());
+
+        let partition = Partition::for_region("cn-north-1").expect("known region");
+        assert_eq!("aws-cn", partition.id());
+    }
+
+    #[test]
+    fn test_every_partition() {
+        const CASES: &[(&str, &str, &str, &str)] = &[
+            ("eu-west-1", "aws", "amazonaws.com", "api.aws"),
+            (
+                "cn-northwest-1",
+                "aws-cn",
+                "amazonaws.com.cn",
+                "api.amazonwebservices.com.cn",
+            ),
+            ("us-gov-west-1", "aws-us-gov", "amazonaws.com", "api.aws"),
+            ("us-iso-east-1", "aws-iso", "c2s.ic.gov", "api.aws.ic.gov"),
+            (
+                "us-isob-east-1",
+                "aws-iso-b",
+                "sc2s.sgov.gov",
+                "api.aws.scloud",
+            ),
+            (
+                "eu-isoe-west-1",
+                "aws-iso-e",
+                "cloud.adc-e.uk",
+                "api.cloud-aws.adc-e.uk",
+            ),
+            (
+                "us-isof-south-1",
+                "aws-iso-f",
+                "csp.hci.ic.gov",
+                "api.aws.hci.ic.gov",
+            ),
+            (
+                "eusc-de-east-1",
+                "aws-eusc",
+                "amazonaws.eu",
+                "api.amazonwebservices.eu",
+            ),
+        ];
+
+        assert_eq!(PARTITIONS.len(), CASES.len());
+        for &(region, id, dns_suffix, dual_stack_dns_suffix) in CASES {
+            let partition = Partition::for_region(region).expect("known region");
+            assert_eq!(id, partition.id(), "{region}");
+            assert_eq!(dns_suffix, partition.dns_suffix(), "{region}");
+            assert_eq!(
+                dual_stack_dns_suffix,
+                partition.dual_stack_dns_suffix(),
+                "{region}"
+            );
+            assert_eq!(partition, Partition::resolve(region), "{region}");
+            assert_eq!(Some(partition), Partition::for_id(id), "{region}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_unknown_region() {
+        assert_eq!("aws", Partition::resolve("us-east-9").id());
+        assert_eq!("aws-cn", Partition::resolve("cn-south-1").id());
+        assert_eq!("aws-us-gov", Partition::resolve("us-gov-central-1").id());
+        assert_eq!("aws-iso", Partition::resolve("us-iso-central-1").id());
+        assert_eq!("aws-iso-b", Partition::resolve("us-isob-west-1").id());
+        assert_eq!("aws-iso-e", Partition::resolve("eu-isoe-east-1").id());
+        assert_eq!("aws-iso-f", Partition::resolve("us-isof-west-1").id());
+        assert_eq!("aws-eusc", Partition::resolve("eusc-fr-west-1").id());
+        assert_eq!("aws", Partition::resolve("mars-north-99").id());
+    }
+
+    #[test]
+    fn test_for_id_unknown() {
+        assert_eq!(None, Partition::for_id("aws-mars"));
+    }
+
+    #[test]
+    fn test_regions_match_own_prefixes() {
+        for partition in PARTITIONS {
+            for region in partition.regions() {
+                assert!(
+                    partition
+                        .region_prefixes
+                        .iter()
+                        .any(|prefix| region.starts_with(prefix)),
+                    "{region}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_for_region_unknown() {
+        assert_eq!(None, Partition::for_region("us-east1"));
+    }
+
+    #[test]
+    fn test_closest_region() {
+        assert_eq!(Some("us-east-1"), closest_region("us-east1"));
+        assert_eq!(Some("ap-southeast-2"), closest_region("ap-southeast2"));
+        assert_eq!(Some("eu-west-1"), closest_region("eu-wset-1"));
+    }
+
+    #[test]
+    fn test_closest_region_none() {
+        assert_eq!(None, closest_region("my-repo"));
+    }
+
+    #[test]
+    fn test_has_fips_endpoint() {
+        assert!(has_fips_endpoint("us-east-1"));
+        assert!(has_fips_endpoint("us-gov-west-1"));
+        assert!(!has_fips_endpoint("eu-west-1"));
+        assert!(!has_fips_endpoint("cn-north-1"));
+    }
+
+    #[test]
+    fn test_fips_regions_are_known() {
+        for region in FIPS_REGIONS {
+            assert!(Partition::for_region(region).is_some(), "{region}");
+        }
+    }
+
+    #[test]
+    fn test_region_for_code() {
+        assert_eq!(Some("ap-southeast-2"), region_for_code("syd"));
+        assert_eq!(Some("us-east-1"), region_for_code("iad"));
+        assert_eq!(None, region_for_code("us-east-1"));
+    }
+
+    #[test]
+    fn test_region_codes_are_known_and_unique() {
+        for (code, region) in REGION_CODES {
+            assert!(Partition::for_region(region).is_some(), "{code}");
+        }
+        assert!(REGION_CODES.is_sorted_by_key(|&(code, _)| code));
+        assert!(REGION_CODES.windows(2).all(|pair| pair[0].0 != pair[1].0));
+    }
+
+    #[test]
+    fn test_regions_are_unique() {
+        let mut regions = PARTITIONS
+            .iter()
+            .flat_map(Partition::regions)
+            .collect::<Vec<_>>();
+        let len = regions.len();
+        regions.sort_unstable();
+        regions.dedup();
+        assert_eq!(len, regions.len());
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use anyhow::Context;
 use aws_config::AppName;
 use aws_config::BehaviorVersion;
@@ -5,33 +7,65 @@ use aws_config::Region;
 use aws_config::SdkConfig;
 use aws_config::meta::region::ProvideRegion;
 use aws_config::meta::region::RegionProviderChain;
+use aws_config::sts::AssumeRoleProvider;
 use aws_credential_types::Credentials;
 use aws_credential_types::provider::ProvideCredentials;
+use aws_credential_types::provider::SharedCredentialsProvider;
+use aws_types::service_config::ServiceConfigKey;
+use tracing::debug;
+
+use crate::signer::CommandSigner;
+use crate::signer::Signer;
 
 const APP_NAME: &str = "git-remote-codecommit";
 
-#[derive(Debug)]
+/// The service ID the SDKs derive `AWS_ENDPOINT_URL_CODECOMMIT` and the
+/// `[services]` section key from.
+const SERVICE_ID: &str = "CodeCommit";
+const ENDPOINT_URL_ENV: &str = "AWS_ENDPOINT_URL";
+const ENDPOINT_URL_PROFILE_KEY: &str = "endpoint_url";
+
+#[derive(Debug, Clone)]
 pub struct SdkContext {
     region: Region,
-    credentials: Credentials,
+    signer: Signer,
+    use_fips: bool,
+    use_dual_stack: bool,
+    endpoint_url: Option<String>,
+}
+
+/// Per-remote overrides applied on top of the ambient AWS configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LoadOptions<'a> {
+    pub region: Option<&'a str>,
+    pub profile: Option<&'a str>,
+    pub assume_role_arn: Option<&'a str>,
+    pub expected_account_id: Option<&'a str>,
+    pub use_fips: Option<bool>,
+    pub use_dual_stack: Option<bool>,
+    pub signer_command: Option<&'a Path>,
 }
 
 impl SdkContext {
-    pub fn load_context_sync(
-        override_region: Option<&str>,
-        override_profile: Option<&str>,
-    ) -> anyhow::Result<Self> {
+    pub fn load_context_sync(options: LoadOptions<'_>) -> anyhow::Result<Self> {
         tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .context("failed to build tokio runtime")?
-            .block_on(Self::load_context(override_region, override_profile))
+            .block_on(Self::load_context(options))
     }
 
-    pub async fn load_context(
-        override_region: Option<&str>,
-        override_profile: Option<&str>,
-    ) -> anyhow::Result<Self> {
+    pub async fn load_context(options: LoadOptions<'_>) -> anyhow::Result<Self> {
+        let LoadOptions {
+            region: override_region,
+            profile: override_profile,
+            assume_role_arn,
+            expected_account_id,
+            use_fips,
+            use_dual_stack,
+            signer_command,
+        } = options;
+
         let mut config_loader = aws_config::ConfigLoader::default()
             .behavior_version(BehaviorVersion::latest())
             .region(region_provider(override_region))
@@ -41,8 +75,51 @@ impl SdkContext {
             config_loader = config_loader.profile_name(profile);
         }
 
-        let sdk_config = config_loader.load().await;
-        Self::from_sdk_config(sdk_config).await
+        // Without an override, the loader reads `AWS_USE_FIPS_ENDPOINT` and
+        // the `use_fips_endpoint` profile setting.
+        if let Some(use_fips) = use_fips {
+            config_loader = config_loader.use_fips(use_fips);
+        }
+
+        // Likewise `AWS_USE_DUALSTACK_ENDPOINT` and `use_dualstack_endpoint`.
+        if let Some(use_dual_stack) = use_dual_stack {
+            config_loader = config_loader.use_dual_stack(use_dual_stack);
+        }
+
+        let mut sdk_config = config_loader.load().await;
+
+        if let Some(role_arn) = assume_role_arn {
+            let provider = AssumeRoleProvider::builder(role_arn)
+                .session_name(APP_NAME)
+                .configure(&sdk_config)
+                .build()
+                .await;
+            sdk_config = sdk_config
+                .into_builder()
+                .credentials_provider(SharedCredentialsProvider::new(provider))
+                .build();
+        }
+
+        let context = match signer_command {
+            Some(program) => {
+                // The credentials stay with the signer, so there are none to
+                // assume a role or ask STS for the caller identity with.
+                anyhow::ensure!(
+                    assume_role_arn.is_none() && expected_account_id.is_none(),
+                    "the `role` and `account` remote options cannot be used with --signer-command"
+                );
+                Self::with_signer(&sdk_config, Signer::Command(CommandSigner::new(program)))?
+            }
+            None => Self::from_sdk_config(sdk_config.clone()).await?,
+        };
+
+        if let Some(expected_account_id) = expected_account_id {
+            context
+                .verify_account_id(&sdk_config, expected_account_id)
+                .await?;
+        }
+
+        Ok(context)
     }
 
     pub async fn from_sdk_config(sdk_config: SdkConfig) -> anyhow::Result<Self> {
@@ -53,21 +130,121 @@ impl SdkContext {
             .await
             .context("failed to resolve credentials")?;
 
+        Self::with_signer(&sdk_config, Signer::Credentials(credentials))
+    }
+
+    fn with_signer(sdk_config: &SdkConfig, signer: Signer) -> anyhow::Result<Self> {
         let region = sdk_config.region().context("region not set")?.clone();
+        let use_fips = sdk_config.use_fips().unwrap_or_default();
+        let use_dual_stack = sdk_config.use_dual_stack().unwrap_or_default();
+        let endpoint_url = configured_endpoint_url(sdk_config);
 
         Ok(Self {
             region,
-            credentials,
+            signer,
+            use_fips,
+            use_dual_stack,
+            endpoint_url,
         })
     }
 
+    /// Checks that the resolved credentials belong to `expected_account_id`
+    /// by asking STS for the caller identity.
+    pub async fn verify_account_id(
+        &self,
+        sdk_config: &SdkConfig,
+        expected_account_id: &str,
+    ) -> anyhow::Result<()> {
+        let credentials = self
+            .credentials()
+            .context("credentials are held by the signer command")?;
+        let sts_config = aws_sdk_sts::config::Builder::from(sdk_config)
+            .region(self.region.clone())
+            .credentials_provider(credentials.clone())
+            .build();
+
+        let identity = aws_sdk_sts::Client::from_conf(sts_config)
+            .get_caller_identity()
+            .send()
+            .await
+            .context("failed to get the caller identity")?;
+
+        let account_id = identity
+            .account()
+            .context("caller identity has no account ID")?;
+        debug!(account_id, expected_account_id, "resolved caller identity");
+
+        anyhow::ensure!(
+            account_id == expected_account_id,
+            "credentials belong to AWS account {account_id}, but the remote expects account {expected_account_id}"
+        );
+
+        Ok(())
+    }
+
     pub fn region(&self) -> &Region {
         &self.region
     }
 
-    pub fn credentials(&self) -> &Credentials {
-        &self.credentials
+    /// The same context for another region, e.g. a failover region of the
+    /// remote.
+    pub fn with_region(&self, region: Region) -> Self {
+        Self {
+            region,
+            ..self.clone()
+        }
     }
+
+    pub fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
+    /// The resolved credentials, or `None` if an external signer holds them.
+    pub fn credentials(&self) -> Option<&Credentials> {
+        match &self.signer {
+            Signer::Credentials(credentials) => Some(credentials),
+            Signer::Command(_) => None,
+        }
+    }
+
+    /// Whether requests should go to the FIPS endpoint of the region.
+    pub fn use_fips(&self) -> bool {
+        self.use_fips
+    }
+
+    /// Whether requests should go to the dual-stack endpoint of the region.
+    pub fn use_dual_stack(&self) -> bool {
+        self.use_dual_stack
+    }
+
+    /// The `CodeCommit` endpoint URL configured through the standard AWS
+    /// sources, if any. See [`configured_endpoint_url`].
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.endpoint_url.as_deref()
+    }
+}
+
+/// Resolves the endpoint URL the AWS SDKs would use for `CodeCommit`, in order
+/// of precedence:
+///
+/// 1. `AWS_ENDPOINT_URL_CODECOMMIT`
+/// 2. `AWS_ENDPOINT_URL`
+/// 3. `endpoint_url` in the `codecommit` section of the profile's `services`
+/// 4. `endpoint_url` in the profile
+///
+/// The service config of the loaded profile resolves all four, so the endpoint
+/// URL of the SDK config is not consulted.
+fn configured_endpoint_url(sdk_config: &SdkConfig) -> Option<String> {
+    let key = ServiceConfigKey::builder()
+        .service_id(SERVICE_ID)
+        .env(ENDPOINT_URL_ENV)
+        .profile(ENDPOINT_URL_PROFILE_KEY)
+        .build()
+        .expect("constant service config key to be valid");
+
+    sdk_config
+        .service_config()
+        .and_then(|service_config| service_config.load_config(key))
 }
 
 fn app_name() -> AppName {
@@ -92,3 +269,46 @@ impl From<Option<&str>> for MaybeRegion {
         Self(value.map(str::to_owned).map(Region::new))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use aws_types::service_config::LoadServiceConfig;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct ServiceEndpoint(&'static str);
+
+    impl LoadServiceConfig for ServiceEndpoint {
+        fn load_config(&self, key: ServiceConfigKey<'_>) -> Option<String> {
+            (key.service_id() == SERVICE_ID && key.profile() == ENDPOINT_URL_PROFILE_KEY)
+                .then(|| self.0.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_configured_endpoint_url_service() {
+        let sdk_config = SdkConfig::builder()
+            .endpoint_url("https://global.example")
+            .service_config(ServiceEndpoint("https://codecommit.example"))
+            .build();
+        assert_eq!(
+            Some("https://codecommit.example".to_owned()),
+            configured_endpoint_url(&sdk_config)
+        );
+    }
+
+    #[test]
+    fn test_configured_endpoint_url_ignored() {
+        let sdk_config = SdkConfig::builder()
+            .endpoint_url("https://global.example")
+            .build();
+        assert_eq!(None, configured_endpoint_url(&sdk_config));
+    }
+
+    #[test]
+    fn test_configured_endpoint_url_unset() {
+        let sdk_config = SdkConfig::builder().build();
+        assert_eq!(None, configured_endpoint_url(&sdk_config));
+    }
+}
@@ -0,0 +1,280 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use anyhow::Context;
+use aws_credential_types::Credentials;
+use codecommit_sigv4::SignerInput;
+use codecommit_sigv4::SignerOutput;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::debug;
+
+/// The version of the JSON protocol spoken with `--signer-command`.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Signs the git credentials of the remote.
+#[derive(Debug, Clone)]
+pub enum Signer {
+    /// Signs in process with credentials resolved from the AWS configuration.
+    Credentials(Credentials),
+    /// Asks an external program, so the secret access key never enters this
+    /// process. See [`CommandSigner`].
+    Command(CommandSigner),
+}
+
+impl codecommit_sigv4::Signer for Signer {
+    type Error = anyhow::Error;
+
+    fn sign(&self, input: &SignerInput<'_>) -> Result<SignerOutput, Self::Error> {
+        match self {
+            Self::Credentials(credentials) => {
+                let credentials = codecommit_sigv4::Credentials::new(
+                    credentials.access_key_id(),
+                    credentials.secret_access_key(),
+                )
+                .with_session_token(credentials.session_token());
+                match codecommit_sigv4::Signer::sign(&credentials, input) {
+                    Ok(output) => Ok(output),
+                    Err(never) => match never {},
+                }
+            }
+            Self::Command(command) => command.sign(input),
+        }
+    }
+}
+
+/// Signs by running a program that holds the credentials.
+///
+/// The program is run without a shell, once per signature. It is sent a
+/// single JSON object on stdin:
+///
+/// ```json
+/// {"version":1,"region":"us-east-1","service":"codecommit","timestamp":"20260101T000000Z","string_to_sign":"AWS4-HMAC-SHA256\n..."}
+/// ```
+///
+/// and must print a single JSON object to stdout with the access key ID, the
+/// session token of temporary credentials, and either the hex-encoded
+/// signature of the string to sign or the hex-encoded `SigV4` signing key for
+/// the date, region and service:
+///
+/// ```json
+/// {"access_key_id":"AKIA...","session_token":null,"signature":"f840ae..."}
+/// ```
+///
+/// Its stderr is passed through, and a non-zero exit status fails the remote.
+/// An agent listening on a Unix socket can be used through a small program
+/// that relays the request to it, e.g. `socat - UNIX-CONNECT:/path/to.sock`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommandSigner {
+    program: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct SignRequest<'a> {
+    version: u32,
+    region: &'a str,
+    service: &'a str,
+    timestamp: &'a str,
+    string_to_sign: &'a str,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct SignResponse {
+    access_key_id: String,
+    #[serde(default)]
+    session_token: Option<String>,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
+    signing_key: Option<String>,
+}
+
+impl CommandSigner {
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+        }
+    }
+
+    fn sign(&self, input: &SignerInput<'_>) -> anyhow::Result<SignerOutput> {
+        let program = self.program.display();
+        let timestamp = input.amz_date();
+        let request = serde_json::to_vec(&SignRequest {
+            version: PROTOCOL_VERSION,
+            region: input.region(),
+            service: input.service(),
+            timestamp: &timestamp,
+            string_to_sign: input.string_to_sign(),
+        })
+        .context("failed to encode signing request")?;
+
+        let mut child = Command::new(&self.program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("failed to spawn signer command {program}"))?;
+        debug!(%program, "spawned signer command");
+
+        let mut stdin = child.stdin.take().context("signer command has no stdin")?;
+        stdin
+            .write_all(&request)
+            .and_then(|()| stdin.write_all(b"\n"))
+            .with_context(|| format!("failed to write to signer command {program}"))?;
+        drop(stdin);
+
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("failed to wait for signer command {program}"))?;
+        anyhow::ensure!(
+            output.status.success(),
+            "signer command {program} failed: {}",
+            output.status
+        );
+
+        parse_response(&output.stdout)
+            .with_context(|| format!("invalid response from signer command {program}"))
+    }
+}
+
+fn parse_response(stdout: &[u8]) -> anyhow::Result<SignerOutput> {
+    let SignResponse {
+        access_key_id,
+        session_token,
+        signature,
+        signing_key,
+    } = serde_json::from_slice(stdout)?;
+
+    let output = match (signature, signing_key) {
+        (Some(signature), None) => {
+            SignerOutput::signature(access_key_id, decode_hex(&signature).context("signature")?)
+        }
+        (None, Some(signing_key)) => SignerOutput::signing_key(
+            access_key_id,
+            decode_hex(&signing_key).context("signing_key")?,
+        ),
+        _ => anyhow::bail!("expected exactly one of `signature` and `signing_key`"),
+    };
+
+    Ok(output.with_session_token(session_token))
+}
+
+/// Decodes 64 hex digits.
+fn decode_hex(value: &str) -> anyhow::Result<[u8; 32]> {
+    anyhow::ensure!(value.len() == 64, "expected 64 hex digits");
+
+    let mut bytes = [0; 32];
+    for (byte, digits) in bytes.iter_mut().zip(value.as_bytes().chunks_exact(2)) {
+        let digits = core::str::from_utf8(digits).context("expected hex digits")?;
+        *byte = u8::from_str_radix(digits, 16).context("expected hex digits")?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use codecommit_sigv4::SigningRequest;
+
+    use super::*;
+
+    const PASSWORD: &str =
+        "19700101T000000Zf840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d";
+
+    fn request() -> SigningRequest<'static> {
+        SigningRequest::new(
+            "us-east-1",
+            "git-codecommit.us-east-1.amazonaws.com",
+            "my-repo",
+        )
+        .with_timestamp(SystemTime::UNIX_EPOCH)
+    }
+
+    #[test]
+    fn test_credentials_signer() {
+        let signer = Signer::Credentials(Credentials::for_tests_with_session_token());
+        let credentials = request().sign_with(&signer).expect("signed");
+        assert_eq!("ANOTREAL%notarealsessiontoken", credentials.username());
+        assert_eq!(PASSWORD, credentials.password());
+    }
+
+    #[test]
+    fn test_parse_response_signature() {
+        let output = parse_response(
+            br#"{"access_key_id":"ANOTREAL","signature":"f840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d"}"#,
+        )
+        .expect("valid response");
+        assert_eq!(
+            SignerOutput::signature(
+                "ANOTREAL",
+                decode_hex("f840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d")
+                    .expect("valid hex"),
+            ),
+            output
+        );
+    }
+
+    #[test]
+    fn test_parse_response_signing_key() {
+        let output = parse_response(
+            br#"{"access_key_id":"ANOTREAL","session_token":"notarealsessiontoken","signing_key":"00112233445566778899aabbccddeeff00112233445566778899AABBCCDDEEFF"}"#,
+        )
+        .expect("valid response");
+        assert_eq!(
+            SignerOutput::signing_key("ANOTREAL", [
+                0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb,
+                0xcc, 0xdd, 0xee, 0xff,
+            ],)
+            .with_session_token(Some("notarealsessiontoken")),
+            output
+        );
+    }
+
+    #[test]
+    fn test_parse_response_invalid() {
+        for response in [
+            "",
+            r#"{"signature":"f840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d"}"#,
+            r#"{"access_key_id":"ANOTREAL"}"#,
+            r#"{"access_key_id":"ANOTREAL","signature":"f840","signing_key":"f840"}"#,
+            r#"{"access_key_id":"ANOTREAL","signature":"f840ae"}"#,
+            r#"{"access_key_id":"ANOTREAL","signature":"zz40ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d"}"#,
+        ] {
+            assert!(parse_response(response.as_bytes()).is_err(), "{response}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_signer() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir =
+            std::env::temp_dir().join(format!("git-remote-codecommit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("created temp dir");
+        let program = dir.join("signer");
+        std::fs::write(
+            &program,
+            "#!/bin/sh\n\
+             grep -q '\"timestamp\":\"19700101T000000Z\"' || exit 1\n\
+             echo '{\"access_key_id\":\"ANOTREAL\",\"signature\":\"f840ae3ff903ddb92c450d0e3567fe97ef4aa98bd6636905df48c3beee97d21d\"}'\n",
+        )
+        .expect("wrote signer");
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755))
+            .expect("made signer executable");
+
+        let signed = request().sign_with(&Signer::Command(CommandSigner::new(&program)));
+        let failed = SigningRequest::new("us-east-1", "localhost", "my-repo")
+            .sign_with(&Signer::Command(CommandSigner::new(&program)));
+        std::fs::remove_dir_all(&dir).expect("removed temp dir");
+
+        let credentials = signed.expect("signed");
+        assert_eq!("ANOTREAL", credentials.username());
+        assert_eq!(PASSWORD, credentials.password());
+        assert!(failed.is_err());
+    }
+}
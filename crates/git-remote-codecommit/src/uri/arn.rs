@@ -0,0 +1,221 @@
+use std::borrow::Cow;
+
+use super::ParseUriError;
+use super::Spans;
+use crate::SERVICE;
+use crate::partition::Partition;
+
+const ARN_PREFIX: &str = "arn";
+
+const ACCOUNT_ID_LEN: usize = 12;
+
+/// A `CodeCommit` repository ARN,
+/// `arn:<partition>:codecommit:<region>:<account-id>:<repository>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Arn<'a> {
+    partition: Cow<'a, str>,
+    region: Cow<'a, str>,
+    account_id: Cow<'a, str>,
+    repository: Cow<'a, str>,
+}
+
+impl<'a> Arn<'a> {
+    /// Returns true if `value` looks like an ARN and should be parsed as one.
+    pub fn is_arn(value: &str) -> bool {
+        value
+            .split_once(':')
+            .is_some_and(|(prefix, _)| prefix == ARN_PREFIX)
+    }
+
+    #[cfg(test)]
+    pub fn new(value: &'a str) -> Result<Self, ParseUriError> {
+        Self::parse(value, &mut Spans::new(value))
+    }
+
+    /// Parses `value`, recording the segment that is invalid in `spans`.
+    pub(super) fn parse(value: &'a str, spans: &mut Spans<'_>) -> Result<Self, ParseUriError> {
+        let mut parts = value.splitn(6, ':');
+        let mut next = || parts.next().filter(|part| !part.is_empty());
+        let (
+            Some(prefix),
+            Some(partition),
+            Some(service),
+            Some(region),
+            Some(account_id),
+            Some(repository),
+        ) = (next(), next(), next(), next(), next(), next())
+        else {
+            return Err(spans.fail(ParseUriError::InvalidArn, value));
+        };
+
+        if prefix != ARN_PREFIX {
+            return Err(spans.fail(ParseUriError::InvalidArn, prefix));
+        }
+
+        if service != SERVICE {
+            return Err(spans.fail(ParseUriError::UnexpectedArnService, service));
+        }
+
+        if Partition::for_id(partition) != Some(Partition::resolve(region)) {
+            return Err(spans.fail(ParseUriError::UnexpectedArnPartition, partition));
+        }
+
+        if !is_account_id(account_id) {
+            return Err(spans.fail(ParseUriError::InvalidArnAccountId, account_id));
+        }
+
+        if repository.contains(['/', ':']) {
+            return Err(spans.fail(ParseUriError::InvalidArn, repository));
+        }
+
+        Ok(Self {
+            partition: Cow::Borrowed(partition),
+            region: Cow::Borrowed(region),
+            account_id: Cow::Borrowed(account_id),
+            repository: Cow::Borrowed(repository),
+        })
+    }
+
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    pub fn into_owned(self) -> Arn<'static> {
+        Arn {
+            partition: Cow::Owned(self.partition.into_owned()),
+            region: Cow::Owned(self.region.into_owned()),
+            account_id: Cow::Owned(self.account_id.into_owned()),
+            repository: Cow::Owned(self.repository.into_owned()),
+        }
+    }
+}
+
+/// Returns true if `value` is a 12-digit AWS account ID.
+pub fn is_account_id(value: &str) -> bool {
+    value.len() == ACCOUNT_ID_LEN && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+impl core::fmt::Display for Arn<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self {
+            partition,
+            region,
+            account_id,
+            repository,
+        } = self;
+        write!(
+            f,
+            "{ARN_PREFIX}:{partition}:{SERVICE}:{region}:{account_id}:{repository}"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_arn() {
+        assert!(Arn::is_arn(
+            "arn:aws:codecommit:us-east-1:123456789012:my-repo"
+        ));
+        assert!(!Arn::is_arn("my-repo"));
+        assert!(!Arn::is_arn("arnold"));
+    }
+
+    #[test]
+    fn test_new() {
+        let arn =
+            Arn::new("arn:aws:codecommit:eu-west-1:123456789012:payments").expect("valid ARN");
+        assert_eq!("eu-west-1", arn.region());
+        assert_eq!("123456789012", arn.account_id());
+        assert_eq!("payments", arn.repository());
+    }
+
+    #[test]
+    fn test_to_string() {
+        const ARN: &str = "arn:aws-cn:codecommit:cn-north-1:123456789012:payments";
+        let arn = Arn::new(ARN).expect("valid ARN");
+        assert_eq!(ARN, arn.to_string());
+    }
+
+    #[test]
+    fn test_missing_parts() {
+        assert_eq!(
+            Err(ParseUriError::InvalidArn),
+            Arn::new("arn:aws:codecommit:eu-west-1:123456789012"),
+        );
+    }
+
+    #[test]
+    fn test_empty_part() {
+        assert_eq!(
+            Err(ParseUriError::InvalidArn),
+            Arn::new("arn:aws:codecommit::123456789012:payments"),
+        );
+    }
+
+    #[test]
+    fn test_unexpected_service() {
+        assert_eq!(
+            Err(ParseUriError::UnexpectedArnService),
+            Arn::new("arn:aws:s3:eu-west-1:123456789012:payments"),
+        );
+    }
+
+    #[test]
+    fn test_every_partition() {
+        for arn in [
+            "arn:aws:codecommit:eu-west-1:123456789012:payments",
+            "arn:aws-cn:codecommit:cn-north-1:123456789012:payments",
+            "arn:aws-us-gov:codecommit:us-gov-west-1:123456789012:payments",
+            "arn:aws-iso:codecommit:us-iso-east-1:123456789012:payments",
+            "arn:aws-iso-b:codecommit:us-isob-east-1:123456789012:payments",
+            "arn:aws-iso-e:codecommit:eu-isoe-west-1:123456789012:payments",
+            "arn:aws-iso-f:codecommit:us-isof-east-1:123456789012:payments",
+            "arn:aws-eusc:codecommit:eusc-de-east-1:123456789012:payments",
+        ] {
+            assert!(Arn::new(arn).is_ok(), "{arn}");
+        }
+    }
+
+    #[test]
+    fn test_unexpected_partition() {
+        assert_eq!(
+            Err(ParseUriError::UnexpectedArnPartition),
+            Arn::new("arn:aws:codecommit:cn-north-1:123456789012:payments"),
+        );
+        assert_eq!(
+            Err(ParseUriError::UnexpectedArnPartition),
+            Arn::new("arn:aws-mars:codecommit:us-east-1:123456789012:payments"),
+        );
+    }
+
+    #[test]
+    fn test_invalid_account_id() {
+        assert_eq!(
+            Err(ParseUriError::InvalidArnAccountId),
+            Arn::new("arn:aws:codecommit:eu-west-1:1234:payments"),
+        );
+        assert_eq!(
+            Err(ParseUriError::InvalidArnAccountId),
+            Arn::new("arn:aws:codecommit:eu-west-1:12345678901x:payments"),
+        );
+    }
+
+    #[test]
+    fn test_resource_path() {
+        assert_eq!(
+            Err(ParseUriError::InvalidArn),
+            Arn::new("arn:aws:codecommit:eu-west-1:123456789012:payments/extra"),
+        );
+    }
+}
@@ -1,16 +1,59 @@
+use std::sync::LazyLock;
+
 use uriparse::URIError;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use super::MAX_REPOSITORY_NAME_LEN;
+use super::options::QUERY_PARAMETERS;
+
+/// Lists the query parameters, e.g. "supported query parameters are `a=<a>`,
+/// `b=<b>` and `c=<c>`".
+static QUERY_PARAMETERS_HINT: LazyLock<String> = LazyLock::new(|| {
+    let mut hint = "supported query parameters are ".to_owned();
+    for (i, (name, value)) in QUERY_PARAMETERS.iter().enumerate() {
+        let sep = match i {
+            0 => "",
+            _ if i + 1 == QUERY_PARAMETERS.len() => " and ",
+            _ => ", ",
+        };
+        hint.extend([sep, "`", name, "=", value, "`"]);
+    }
+    hint
+});
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ParseUriError {
     InvalidUri(URIError),
     MissingAuthority,
     UnexpectedPath,
-    UnexpectedQuery,
+    UnknownQueryParameter(String),
+    DuplicateQueryParameter(&'static str),
+    InvalidQueryParameter(&'static str),
     UnexpectedFragment,
     UnexpectedPassword,
     UnexpectedPort,
+    UnexpectedUserInfo,
+    UnrecognizedHost,
+    InvalidArn,
+    UnexpectedArnService,
+    UnexpectedArnPartition,
+    InvalidArnAccountId,
+    ConflictingRegion,
+    ConflictingAccountId,
+    InvalidRegionList,
+    UnknownAlias {
+        alias: String,
+        known: Vec<String>,
+    },
+    UnknownRegion {
+        region: String,
+        suggestion: Option<&'static str>,
+    },
     UnexpectedIpForRepositoryName,
     EmptyRepositoryName,
+    InvalidPercentEncoding,
+    InvalidRepositoryNameCharacter(char),
+    RepositoryNameTooLong(usize),
+    RepositoryNameEndsWithGit,
 }
 
 impl From<URIError> for ParseUriError {
@@ -19,18 +62,133 @@ impl From<URIError> for ParseUriError {
     }
 }
 
+impl ParseUriError {
+    /// A suggestion for how to write the remote URI correctly.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::UnexpectedPassword => {
+                Some("profiles go before the @, regions go in `codecommit::<region>://`")
+            }
+            Self::UnexpectedPath => {
+                Some("regions go in `codecommit::<region>://`, not in the path")
+            }
+            Self::InvalidUri(URIError::Scheme(_)) => {
+                Some("the scheme is `codecommit`, a region or a comma-separated list of regions")
+            }
+            Self::InvalidUri(
+                URIError::Authority(_)
+                | URIError::Path(_)
+                | URIError::Query(_)
+                | URIError::Fragment(_),
+            ) => Some("percent-encode characters that are not allowed in URIs, e.g. `%20`"),
+            Self::InvalidUri(_)
+            | Self::MissingAuthority
+            | Self::UnexpectedIpForRepositoryName
+            | Self::EmptyRepositoryName => Some(
+                "the repository name goes right after `://`, e.g. `codecommit::<region>://<profile>@<repository>`",
+            ),
+            Self::UnknownQueryParameter(_)
+            | Self::DuplicateQueryParameter(_)
+            | Self::InvalidQueryParameter(_) => Some(QUERY_PARAMETERS_HINT.as_str()),
+            Self::UnexpectedFragment => Some("remove the `#` and everything after it"),
+            Self::UnexpectedPort => {
+                Some("to use a custom endpoint, add `?endpoint=<hostname>:<port>`")
+            }
+            Self::UnexpectedUserInfo => Some(
+                "HTTPS URLs use the default profile; use `codecommit::<region>://<profile>@<repository>` instead",
+            ),
+            Self::UnrecognizedHost => Some(
+                "expected `https://git-codecommit.<region>.amazonaws.com/v1/repos/<repository>`",
+            ),
+            Self::InvalidArn
+            | Self::UnexpectedArnService
+            | Self::UnexpectedArnPartition
+            | Self::InvalidArnAccountId => {
+                Some("expected `arn:<partition>:codecommit:<region>:<account-id>:<repository>`")
+            }
+            Self::ConflictingRegion => Some(
+                "drop the region from `codecommit::<region>://`; the ARN or alias already sets it",
+            ),
+            Self::ConflictingAccountId => {
+                Some("drop the `account` query parameter; the ARN or alias already sets it")
+            }
+            Self::InvalidRegionList => Some(
+                "list the primary region first and each region once, e.g. `codecommit::us-east-1,us-west-2://<repository>`",
+            ),
+            Self::UnknownAlias { .. } => {
+                Some("aliases are defined in the file given by `--aliases-file`")
+            }
+            Self::UnknownRegion { .. } => {
+                Some("pass `--allow-unknown-region` for regions newer than this binary")
+            }
+            Self::RepositoryNameEndsWithGit => Some("drop the `.git` suffix"),
+            Self::InvalidPercentEncoding
+            | Self::InvalidRepositoryNameCharacter(_)
+            | Self::RepositoryNameTooLong(_) => None,
+        }
+    }
+}
+
 impl core::fmt::Display for ParseUriError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidUri(err) => write!(f, "invalid URI: {err}"),
             Self::MissingAuthority => f.write_str("missing authority"),
             Self::UnexpectedPath => f.write_str("unexpected path"),
-            Self::UnexpectedQuery => f.write_str("unexpected query"),
+            Self::UnknownQueryParameter(name) => write!(f, "unknown query parameter `{name}`"),
+            Self::DuplicateQueryParameter(name) => {
+                write!(f, "query parameter `{name}` given more than once")
+            }
+            Self::InvalidQueryParameter(name) => {
+                write!(f, "invalid value for query parameter `{name}`")
+            }
             Self::UnexpectedFragment => f.write_str("unexpected fragment"),
             Self::UnexpectedPassword => f.write_str("unexpected password"),
             Self::UnexpectedPort => f.write_str("unexpected port"),
+            Self::UnexpectedUserInfo => f.write_str("unexpected user info"),
+            Self::UnrecognizedHost => f.write_str("unrecognized CodeCommit host"),
+            Self::InvalidArn => f.write_str("invalid repository ARN"),
+            Self::UnexpectedArnService => f.write_str("ARN is not a CodeCommit repository"),
+            Self::UnexpectedArnPartition => f.write_str("ARN partition does not match the region"),
+            Self::InvalidArnAccountId => f.write_str("invalid account ID in ARN"),
+            Self::ConflictingRegion => f.write_str("region does not match the ARN or alias"),
+            Self::ConflictingAccountId => f.write_str("account ID does not match the ARN or alias"),
+            Self::InvalidRegionList => f.write_str("invalid region list"),
+            Self::UnknownAlias { alias, known } if known.is_empty() => {
+                write!(
+                    f,
+                    "unknown repository alias `{alias}`; no aliases are defined"
+                )
+            }
+            Self::UnknownAlias { alias, known } => write!(
+                f,
+                "unknown repository alias `{alias}`; known aliases: {}",
+                known.join(", ")
+            ),
+            Self::UnknownRegion {
+                region,
+                suggestion: Some(suggestion),
+            } => write!(f, "unknown region `{region}`; did you mean `{suggestion}`?"),
+            Self::UnknownRegion {
+                region,
+                suggestion: None,
+            } => write!(f, "unknown region `{region}`"),
             Self::UnexpectedIpForRepositoryName => f.write_str("unexpected IP for repository name"),
             Self::EmptyRepositoryName => f.write_str("empty repository name"),
+            Self::InvalidPercentEncoding => {
+                f.write_str("percent-encoding does not decode to UTF-8")
+            }
+            Self::InvalidRepositoryNameCharacter(c) => write!(
+                f,
+                "invalid character {c:?} in repository name; only letters, digits, `.`, `_` and `-` are allowed"
+            ),
+            Self::RepositoryNameTooLong(len) => write!(
+                f,
+                "repository name is {len} characters long; the maximum is {MAX_REPOSITORY_NAME_LEN}"
+            ),
+            Self::RepositoryNameEndsWithGit => {
+                f.write_str("repository name must not end with `.git`")
+            }
         }
     }
 }
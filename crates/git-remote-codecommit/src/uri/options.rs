@@ -0,0 +1,438 @@
+#![cfg_attr(not(bool_to_result), allow(unstable_name_collisions))]
+
+use super::ParseUriError;
+use super::Spans;
+use super::arn::is_account_id;
+use crate::hostname::CliHostname;
+#[cfg(not(bool_to_result))]
+use crate::nightly::BoolExt;
+use crate::urlsafe::UrlSafeQuote;
+use crate::urlsafe::percent_decode;
+
+const ENDPOINT: &str = "endpoint";
+const ROLE: &str = "role";
+const ACCOUNT: &str = "account";
+const FIPS: &str = "fips";
+const DUAL_STACK: &str = "dualstack";
+const SIGNING_HOST: &str = "signinghost";
+
+const ARN_PREFIX: &str = "arn:";
+
+/// Every query parameter with the form of its value, in documentation order.
+pub const QUERY_PARAMETERS: &[(&str, &str)] = &[
+    (ENDPOINT, "[<scheme>://]<hostname>[:<port>]"),
+    (ROLE, "<role-arn>"),
+    (ACCOUNT, "<account-id>"),
+    (FIPS, "<true|false>"),
+    (DUAL_STACK, "<true|false>"),
+    (SIGNING_HOST, "<hostname>[:<port>]"),
+];
+
+/// Per-remote settings carried in the query string of the remote URI, e.g.
+/// `codecommit://repo?endpoint=localhost:8443&role=<role-arn>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RemoteOptions {
+    endpoint: Option<CliHostname>,
+    role_arn: Option<String>,
+    account_id: Option<String>,
+    use_fips: Option<bool>,
+    use_dual_stack: Option<bool>,
+    signing_host: Option<CliHostname>,
+}
+
+impl RemoteOptions {
+    /// The `CodeCommit` endpoint to use for this remote.
+    pub fn endpoint(&self) -> Option<&CliHostname> {
+        self.endpoint.as_ref()
+    }
+
+    /// The ARN of a role to assume before signing requests for this remote.
+    pub fn role_arn(&self) -> Option<&str> {
+        self.role_arn.as_deref()
+    }
+
+    /// The AWS account the resolved credentials must belong to.
+    pub fn account_id(&self) -> Option<&str> {
+        self.account_id.as_deref()
+    }
+
+    /// Whether to use the FIPS endpoint. `None` defers to the AWS config.
+    pub fn use_fips(&self) -> Option<bool> {
+        self.use_fips
+    }
+
+    /// Whether to use the dual-stack endpoint. `None` defers to the AWS
+    /// config.
+    pub fn use_dual_stack(&self) -> Option<bool> {
+        self.use_dual_stack
+    }
+
+    /// The host to sign requests for, if not the one connected to.
+    pub fn signing_host(&self) -> Option<&CliHostname> {
+        self.signing_host.as_ref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let Self {
+            endpoint,
+            role_arn,
+            account_id,
+            use_fips,
+            use_dual_stack,
+            signing_host,
+        } = self;
+        endpoint.is_none()
+            && role_arn.is_none()
+            && account_id.is_none()
+            && use_fips.is_none()
+            && use_dual_stack.is_none()
+            && signing_host.is_none()
+    }
+
+    /// Parses a query string, recording the parameter that is invalid in
+    /// `spans`.
+    pub(super) fn parse(query: &str, spans: &mut Spans<'_>) -> Result<Self, ParseUriError> {
+        let mut options = Self::default();
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let name = percent_decode(name).ok_or_else(|| {
+                spans.fail(ParseUriError::UnknownQueryParameter(name.to_owned()), pair)
+            })?;
+            if name == ACCOUNT {
+                spans.account(pair);
+            }
+            let value = percent_decode(value);
+            options
+                .insert(&name, value.as_deref())
+                .map_err(|err| spans.fail(err, pair))?;
+        }
+
+        Ok(options)
+    }
+
+    /// Sets the option `name` to an already decoded `value`.
+    ///
+    /// A `None` value is reported as invalid for known options.
+    pub fn insert(&mut self, name: &str, value: Option<&str>) -> Result<(), ParseUriError> {
+        match name {
+            ENDPOINT => set(
+                &mut self.endpoint,
+                ENDPOINT,
+                value.and_then(|value| value.parse().ok()),
+            ),
+            ROLE => set(
+                &mut self.role_arn,
+                ROLE,
+                value
+                    .filter(|value| value.starts_with(ARN_PREFIX))
+                    .map(str::to_owned),
+            ),
+            ACCOUNT => set(
+                &mut self.account_id,
+                ACCOUNT,
+                value
+                    .filter(|value| is_account_id(value))
+                    .map(str::to_owned),
+            ),
+            FIPS => set(&mut self.use_fips, FIPS, value.and_then(parse_bool)),
+            DUAL_STACK => set(
+                &mut self.use_dual_stack,
+                DUAL_STACK,
+                value.and_then(parse_bool),
+            ),
+            SIGNING_HOST => set(
+                &mut self.signing_host,
+                SIGNING_HOST,
+                value.and_then(|value| CliHostname::from_signing_host(value).ok()),
+            ),
+            _ => Err(ParseUriError::UnknownQueryParameter(name.to_owned())),
+        }
+    }
+
+    /// Sets the expected account ID from another source, such as a repository
+    /// alias. An account ID that is already set must match.
+    pub fn merge_account_id(&mut self, account_id: &str) -> Result<(), ParseUriError> {
+        match self.account_id() {
+            Some(existing) => (existing == account_id).ok_or(ParseUriError::ConflictingAccountId),
+            None => self.insert(ACCOUNT, Some(account_id)),
+        }
+    }
+}
+
+/// Parses a boolean the way git config does, e.g. `yes` or `off`.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn set<T>(slot: &mut Option<T>, name: &'static str, value: Option<T>) -> Result<(), ParseUriError> {
+    let value = value.ok_or(ParseUriError::InvalidQueryParameter(name))?;
+    slot.is_none()
+        .ok_or(ParseUriError::DuplicateQueryParameter(name))?;
+    *slot = Some(value);
+    Ok(())
+}
+
+impl core::fmt::Display for RemoteOptions {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self {
+            endpoint,
+            role_arn,
+            account_id,
+            use_fips,
+            use_dual_stack,
+            signing_host,
+        } = self;
+        let mut sep = "";
+
+        if let Some(endpoint) = endpoint {
+            write!(f, "{sep}{ENDPOINT}={}", UrlSafeQuote(&endpoint.to_string()))?;
+            sep = "&";
+        }
+
+        if let Some(role_arn) = role_arn {
+            write!(f, "{sep}{ROLE}={}", UrlSafeQuote(role_arn))?;
+            sep = "&";
+        }
+
+        if let Some(account_id) = account_id {
+            write!(f, "{sep}{ACCOUNT}={account_id}")?;
+            sep = "&";
+        }
+
+        if let Some(use_fips) = use_fips {
+            write!(f, "{sep}{FIPS}={use_fips}")?;
+            sep = "&";
+        }
+
+        if let Some(use_dual_stack) = use_dual_stack {
+            write!(f, "{sep}{DUAL_STACK}={use_dual_stack}")?;
+            sep = "&";
+        }
+
+        if let Some(signing_host) = signing_host {
+            write!(
+                f,
+                "{sep}{SIGNING_HOST}={}",
+                UrlSafeQuote(&signing_host.to_string())
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(query: &str) -> Result<RemoteOptions, ParseUriError> {
+        RemoteOptions::parse(query, &mut Spans::new(query))
+    }
+
+    #[test]
+    fn test_query_parameters_are_known() {
+        for (name, _) in QUERY_PARAMETERS {
+            assert_eq!(
+                Err(ParseUriError::InvalidQueryParameter(name)),
+                RemoteOptions::default().insert(name, None),
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let options = parse("").expect("valid query");
+        assert!(options.is_empty());
+        assert_eq!("", options.to_string());
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let options = parse("endpoint=localhost:8443").expect("valid query");
+        assert_eq!(
+            Some("localhost:8443".to_owned()),
+            options.endpoint().map(ToString::to_string)
+        );
+        assert_eq!(None, options.role_arn());
+    }
+
+    #[test]
+    fn test_endpoint_with_scheme() {
+        let options = parse("endpoint=http%3A%2F%2F%5B%3A%3A1%5D%3A8080").expect("valid query");
+        assert_eq!(
+            Some("http://[::1]:8080".to_owned()),
+            options.endpoint().map(ToString::to_string)
+        );
+        let reparsed = parse(&options.to_string()).expect("valid query");
+        assert_eq!(options, reparsed);
+    }
+
+    #[test]
+    fn test_role() {
+        let options = parse("role=arn:aws:iam::123456789012:role/git").expect("valid query");
+        assert_eq!(None, options.endpoint());
+        assert_eq!(
+            Some("arn:aws:iam::123456789012:role/git"),
+            options.role_arn()
+        );
+    }
+
+    #[test]
+    fn test_account() {
+        let options = parse("account=123456789012").expect("valid query");
+        assert_eq!(Some("123456789012"), options.account_id());
+    }
+
+    #[test]
+    fn test_invalid_account() {
+        assert_eq!(
+            Err(ParseUriError::InvalidQueryParameter(ACCOUNT)),
+            parse("account=1234"),
+        );
+    }
+
+    #[test]
+    fn test_fips() {
+        let options = parse("fips=true").expect("valid query");
+        assert_eq!(Some(true), options.use_fips());
+        let options = parse("fips=false").expect("valid query");
+        assert_eq!(Some(false), options.use_fips());
+    }
+
+    #[test]
+    fn test_fips_git_boolean() {
+        for value in ["yes", "on", "1", "TRUE"] {
+            let options = parse(&format!("fips={value}")).expect("valid query");
+            assert_eq!(Some(true), options.use_fips(), "{value}");
+        }
+        for value in ["no", "off", "0", "False"] {
+            let options = parse(&format!("fips={value}")).expect("valid query");
+            assert_eq!(Some(false), options.use_fips(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_dual_stack() {
+        let options = parse("dualstack=true").expect("valid query");
+        assert_eq!(Some(true), options.use_dual_stack());
+        let options = parse("dualstack=on").expect("valid query");
+        assert_eq!(Some(true), options.use_dual_stack());
+        let options = parse("dualstack=0").expect("valid query");
+        assert_eq!(Some(false), options.use_dual_stack());
+        assert_eq!(
+            Err(ParseUriError::InvalidQueryParameter(DUAL_STACK)),
+            parse("dualstack=maybe"),
+        );
+    }
+
+    #[test]
+    fn test_signing_host() {
+        let options = parse(
+            "endpoint=vpce-0abc-xyz.git-codecommit.us-east-1.vpce.amazonaws.com&signinghost=git-codecommit.us-east-1.amazonaws.com",
+        )
+        .expect("valid query");
+        assert_eq!(
+            Some("vpce-0abc-xyz.git-codecommit.us-east-1.vpce.amazonaws.com".to_owned()),
+            options.endpoint().map(ToString::to_string)
+        );
+        assert_eq!(
+            Some("git-codecommit.us-east-1.amazonaws.com".to_owned()),
+            options.signing_host().map(ToString::to_string)
+        );
+    }
+
+    #[test]
+    fn test_signing_host_with_scheme() {
+        for query in [
+            "signinghost=https%3A%2F%2Fgit-codecommit.us-east-1.amazonaws.com",
+            "signinghost=http%3A%2F%2Flocalhost%3A8443",
+        ] {
+            assert_eq!(
+                Err(ParseUriError::InvalidQueryParameter(SIGNING_HOST)),
+                parse(query),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_fips() {
+        assert_eq!(
+            Err(ParseUriError::InvalidQueryParameter(FIPS)),
+            parse("fips=maybe"),
+        );
+    }
+
+    #[test]
+    fn test_merge_account_id() {
+        let mut options = RemoteOptions::default();
+        options
+            .merge_account_id("123456789012")
+            .expect("valid account ID");
+        assert_eq!(Some("123456789012"), options.account_id());
+        options
+            .merge_account_id("123456789012")
+            .expect("same account ID");
+        assert_eq!(
+            Err(ParseUriError::ConflictingAccountId),
+            options.merge_account_id("210987654321"),
+        );
+    }
+
+    #[test]
+    fn test_percent_encoded() {
+        let options =
+            parse("role=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fgit").expect("valid query");
+        assert_eq!(
+            Some("arn:aws:iam::123456789012:role/git"),
+            options.role_arn()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let options = parse(
+            "endpoint=localhost:8443&role=arn:aws:iam::123456789012:role/git&account=123456789012&fips=true&dualstack=true&signinghost=localhost",
+        )
+        .expect("valid query");
+        let reparsed = parse(&options.to_string()).expect("valid query");
+        assert_eq!(options, reparsed);
+    }
+
+    #[test]
+    fn test_unknown_parameter() {
+        assert_eq!(
+            Err(ParseUriError::UnknownQueryParameter("color".to_owned())),
+            parse("color=blue"),
+        );
+    }
+
+    #[test]
+    fn test_duplicate_parameter() {
+        assert_eq!(
+            Err(ParseUriError::DuplicateQueryParameter(ENDPOINT)),
+            parse("endpoint=a.example&endpoint=b.example"),
+        );
+    }
+
+    #[test]
+    fn test_invalid_endpoint() {
+        assert_eq!(
+            Err(ParseUriError::InvalidQueryParameter(ENDPOINT)),
+            parse("endpoint=-bad-"),
+        );
+    }
+
+    #[test]
+    fn test_invalid_role() {
+        assert_eq!(
+            Err(ParseUriError::InvalidQueryParameter(ROLE)),
+            parse("role=git"),
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 pub struct UrlSafeQuote<'a>(pub &'a str);
 
 impl core::fmt::Display for UrlSafeQuote<'_> {
@@ -24,6 +26,39 @@ impl core::fmt::Display for UrlSafeQuote<'_> {
     }
 }
 
+/// Decodes `%XX` escapes in `value`.
+///
+/// Returns `None` if an escape is malformed or the decoded bytes are not valid
+/// UTF-8.
+pub fn percent_decode(value: &str) -> Option<Cow<'_, str>> {
+    if !value.contains('%') {
+        return Some(Cow::Borrowed(value));
+    }
+
+    let mut bytes = value.bytes();
+    let mut decoded = Vec::with_capacity(value.len());
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let hi = hex_digit(bytes.next()?)?;
+            let lo = hex_digit(bytes.next()?)?;
+            decoded.push((hi << 4) | lo);
+        } else {
+            decoded.push(b);
+        }
+    }
+
+    String::from_utf8(decoded).ok().map(Cow::Owned)
+}
+
+const fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
 const fn is_urlsafe(c: char) -> bool {
     // "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_.-~";
     matches!(c, 'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '.' | '-' | '~')
@@ -51,4 +86,39 @@ mod tests {
         let s = UrlSafeQuote("abc/123/🦀").to_string();
         assert_eq!(s, "abc%2F123%2F%F0%9F%A6%80");
     }
+
+    #[test]
+    fn test_decode_without_escapes() {
+        let s = percent_decode("abc-123").expect("valid escapes");
+        assert!(matches!(s, Cow::Borrowed("abc-123")));
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        const RAW: &str = "abc/123/🦀 @:";
+        let quoted = UrlSafeQuote(RAW).to_string();
+        let s = percent_decode(&quoted).expect("valid escapes");
+        assert_eq!(s, RAW);
+    }
+
+    #[test]
+    fn test_decode_lowercase_hex() {
+        let s = percent_decode("abc%2f123").expect("valid escapes");
+        assert_eq!(s, "abc/123");
+    }
+
+    #[test]
+    fn test_decode_truncated_escape() {
+        assert_eq!(None, percent_decode("abc%2"));
+    }
+
+    #[test]
+    fn test_decode_invalid_hex() {
+        assert_eq!(None, percent_decode("abc%zz"));
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8() {
+        assert_eq!(None, percent_decode("abc%FF"));
+    }
 }